@@ -11,4 +11,29 @@ pub enum OFTError {
     InvalidFee,
     InvalidMintAuthority,
     Paused,
+    TimelockRequired,
+    TimelockNotElapsed,
+    InvalidTimelockDelay,
+    InvalidPeer,
+    NotMoreRestrictive,
+    InvalidPayload,
+    InvalidPauseExpiry,
+    InvalidOFTLimits,
+    InvalidAmount,
+    InvalidFeeDistribution,
+    InvalidFeeRecipient,
+    InvalidFeeVault,
+    InvalidReferral,
+    InvalidRateLimiter,
+    InvalidTimestamp,
+    InvalidTransferHook,
+    InvalidTokenSource,
+    InvalidNativeSol,
+    NotPaused,
+    InvalidOFTType,
+    InvalidEscrow,
+    InsufficientEscrow,
+    InvalidPendingChange,
+    AccountNotMigrated,
+    InvalidApprovedGuids,
 }
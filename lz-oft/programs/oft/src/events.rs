@@ -7,6 +7,9 @@ pub struct OFTSent {
     pub from: Pubkey,
     pub amount_sent_ld: u64,
     pub amount_received_ld: u64,
+    pub oft_fee_ld: u64,
+    pub referral_fee_ld: u64, // part of oft_fee_ld paid to the referrer
+    pub oft_native_fee: u64,
 }
 
 #[event]
@@ -15,4 +18,34 @@ pub struct OFTReceived {
     pub src_eid: u32,
     pub to: Pubkey,
     pub amount_received_ld: u64,
+    pub ata_rent_fee_ld: u64, // deducted from the amount to reimburse the creation of the ATA
+}
+
+#[event]
+pub struct OFTFeesDistributed {
+    pub amount_ld: u64,
+    pub total_distributed_ld: u64,
+}
+
+#[event]
+pub struct OFTCircuitTripped {
+    pub guid: [u8; 32],
+    pub src_eid: u32,
+    pub amount_received_ld: u64,
+    pub volume: u64,
+    pub threshold: u64,
+}
+
+#[event]
+pub struct OFTTypeMigrated {
+    pub oft_type: OFTType,
+    pub tvl_burned_ld: u64, // the locked tokens, minted again on receive from now on
+}
+
+#[event]
+pub struct OFTEscrowMigrated {
+    pub old_escrow: Pubkey,
+    pub new_escrow: Pubkey,
+    pub amount_ld: u64,
+    pub tvl_ld: u64,
 }
@@ -0,0 +1,37 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: AddFeeExemptionParams)]
+pub struct AddFeeExemption<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeeExemption::INIT_SPACE,
+        seeds = [FEE_EXEMPTION_SEED, oft_store.key().as_ref(), params.sender.as_ref()],
+        bump
+    )]
+    pub fee_exemption: Account<'info, FeeExemption>,
+    pub system_program: Program<'info, System>,
+}
+
+impl AddFeeExemption<'_> {
+    pub fn apply(ctx: &mut Context<AddFeeExemption>, params: &AddFeeExemptionParams) -> Result<()> {
+        ctx.accounts.fee_exemption.oft_store = ctx.accounts.oft_store.key();
+        ctx.accounts.fee_exemption.sender = params.sender;
+        ctx.accounts.fee_exemption.bump = ctx.bumps.fee_exemption;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct AddFeeExemptionParams {
+    pub sender: Pubkey,
+}
@@ -0,0 +1,37 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CancelChange<'info> {
+    /// admin or guardian
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        constraint = is_valid_signer(signer.key(), &oft_store) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [
+            PENDING_CHANGE_SEED,
+            oft_store.key().as_ref(),
+            &pending_change.nonce.to_be_bytes()
+        ],
+        bump = pending_change.bump,
+        close = payer
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+    /// CHECK: receives the rent of the pending change
+    #[account(mut, address = pending_change.payer)]
+    pub payer: AccountInfo<'info>,
+}
+
+impl CancelChange<'_> {
+    pub fn apply(_ctx: &mut Context<CancelChange>) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn is_valid_signer(signer: Pubkey, oft_store: &OFTStore) -> bool {
+    oft_store.admin == signer || oft_store.guardian == Some(signer)
+}
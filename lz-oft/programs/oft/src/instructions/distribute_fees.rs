@@ -0,0 +1,99 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Anyone can distribute the accrued fees. The token accounts of the recipients are passed as
+/// remaining accounts in the order of FeeDistribution.recipients, each followed by the transfer hook
+/// accounts of the transfer to it, if the mint has a hook.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [FEE_DISTRIBUTION_SEED, oft_store.key().as_ref()],
+        bump = fee_distribution.bump
+    )]
+    pub fee_distribution: Account<'info, FeeDistribution>,
+    #[account(
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        address = oft_store.token_escrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DistributeFees<'info> {
+    pub fn apply(ctx: &mut Context<'_, '_, '_, 'info, DistributeFees<'info>>) -> Result<()> {
+        let token_mint = ctx.accounts.token_mint.to_account_info();
+        let recipients = &ctx.accounts.fee_distribution.recipients;
+        let mut remaining_accounts = ctx.remaining_accounts;
+        let mut recipient_accounts = Vec::with_capacity(recipients.len());
+        for recipient in recipients {
+            let (token_dest, accounts) =
+                remaining_accounts.split_first().ok_or(OFTError::InvalidFeeRecipient)?;
+            require_keys_eq!(
+                token_dest.key(),
+                recipient.token_account,
+                OFTError::InvalidFeeRecipient
+            );
+            let (hook_accounts, accounts) = transfer_hook::split_accounts(&token_mint, accounts)?;
+            recipient_accounts.push((token_dest, hook_accounts));
+            remaining_accounts = accounts;
+        }
+        require!(remaining_accounts.is_empty(), OFTError::InvalidFeeRecipient);
+
+        let surplus_ld = ctx
+            .accounts
+            .token_escrow
+            .amount
+            .checked_sub(ctx.accounts.oft_store.tvl_ld)
+            .ok_or(OFTError::InsufficientEscrow)?;
+        let shares = ctx.accounts.fee_distribution.split(surplus_ld);
+        let seeds: &[&[u8]] = &[
+            OFT_SEED,
+            &ctx.accounts.oft_store.escrow_seed.to_bytes(),
+            &[ctx.accounts.oft_store.bump],
+        ];
+        let mut amount_ld = 0;
+        for (share_ld, (token_dest, hook_accounts)) in shares.into_iter().zip(recipient_accounts) {
+            if share_ld == 0 {
+                continue;
+            }
+            transfer_hook::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.token_escrow.to_account_info(),
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        to: token_dest.clone(),
+                        authority: ctx.accounts.oft_store.to_account_info(),
+                    },
+                )
+                .with_signer(&[&seeds]),
+                share_ld,
+                ctx.accounts.token_mint.decimals,
+                hook_accounts,
+            )?;
+            amount_ld += share_ld;
+        }
+
+        ctx.accounts.fee_distribution.total_distributed_ld += amount_ld;
+        emit_cpi!(OFTFeesDistributed {
+            amount_ld,
+            total_distributed_ld: ctx.accounts.fee_distribution.total_distributed_ld,
+        });
+        Ok(())
+    }
+}
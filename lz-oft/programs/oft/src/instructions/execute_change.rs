@@ -0,0 +1,92 @@
+use crate::*;
+
+/// Anyone can execute a pending change once its delay has elapsed.
+#[derive(Accounts)]
+pub struct ExecuteChange<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [
+            PENDING_CHANGE_SEED,
+            oft_store.key().as_ref(),
+            &pending_change.nonce.to_be_bytes()
+        ],
+        bump = pending_change.bump,
+        close = payer
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+    /// CHECK: receives the rent of the pending change
+    #[account(mut, address = pending_change.payer)]
+    pub payer: AccountInfo<'info>,
+    // Only required for peer config changes
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + PeerConfig::INIT_SPACE,
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &pending_change.change.remote_eid().unwrap_or_default().to_be_bytes()
+        ],
+        bump
+    )]
+    pub peer: Option<Account<'info, PeerConfig>>,
+    // Only required for fee distribution changes
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + FeeDistribution::INIT_SPACE,
+        seeds = [FEE_DISTRIBUTION_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub fee_distribution: Option<Account<'info, FeeDistribution>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ExecuteChange<'_> {
+    pub fn apply(ctx: &mut Context<ExecuteChange>) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= ctx.accounts.pending_change.execute_after,
+            OFTError::TimelockNotElapsed
+        );
+
+        match ctx.accounts.pending_change.change.clone() {
+            ConfigChange::OFTConfig(params) => SetOFTConfig::update_config(
+                &mut ctx.accounts.oft_store,
+                ctx.remaining_accounts,
+                &params,
+            ),
+            ConfigChange::PeerConfig(params) => {
+                let peer = ctx.accounts.peer.as_mut().ok_or(OFTError::InvalidPeer)?;
+                SetPeerConfig::update_config(peer, &params.config)?;
+                peer.bump = ctx.bumps.peer;
+                peer.version = ACCOUNT_VERSION;
+                Ok(())
+            },
+            ConfigChange::FeeDistribution(params) => {
+                let fee_distribution = ctx
+                    .accounts
+                    .fee_distribution
+                    .as_mut()
+                    .ok_or(OFTError::InvalidFeeDistribution)?;
+                SetFeeDistribution::update_config(
+                    fee_distribution,
+                    &ctx.accounts.oft_store,
+                    &params,
+                )?;
+                fee_distribution.bump = ctx.bumps.fee_distribution;
+                Ok(())
+            },
+            ConfigChange::MigrateOFTType | ConfigChange::MigrateEscrow { .. } => {
+                err!(OFTError::InvalidPendingChange)
+            },
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::*;
+
+/// The guardian is a hot key for incident response. It can only make the OFT more restrictive:
+/// pause it, disable a peer or lower a peer's rate limits.
+#[derive(Accounts)]
+#[instruction(params: GuardParams)]
+pub struct Guard<'info> {
+    pub guardian: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.guardian == Some(guardian.key()) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    // Only required for peer actions
+    #[account(
+        mut,
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid().unwrap_or_default().to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Option<Account<'info, PeerConfig>>,
+}
+
+impl Guard<'_> {
+    pub fn apply(ctx: &mut Context<Guard>, params: &GuardParams) -> Result<()> {
+        match params {
+            GuardParams::Pause => {
+                ctx.accounts.oft_store.paused = true;
+                ctx.accounts.oft_store.paused_until = None;
+            },
+            GuardParams::DisablePeer { .. } => {
+                let peer = ctx.accounts.peer.as_mut().ok_or(OFTError::InvalidPeer)?;
+                peer.outbound_paused = true;
+                peer.outbound_paused_until = None;
+                peer.inbound_paused = true;
+                peer.inbound_paused_until = None;
+            },
+            GuardParams::OutboundRateLimit { params, .. } => {
+                let peer = ctx.accounts.peer.as_mut().ok_or(OFTError::InvalidPeer)?;
+                require!(
+                    params.tightens(&peer.outbound_rate_limiter),
+                    OFTError::NotMoreRestrictive
+                );
+                Self::tighten_rate_limiter(&mut peer.outbound_rate_limiter, params)?;
+            },
+            GuardParams::InboundRateLimit { params, .. } => {
+                let peer = ctx.accounts.peer.as_mut().ok_or(OFTError::InvalidPeer)?;
+                require!(params.tightens(&peer.inbound_rate_limiter), OFTError::NotMoreRestrictive);
+                Self::tighten_rate_limiter(&mut peer.inbound_rate_limiter, params)?;
+            },
+        }
+        Ok(())
+    }
+
+    // Unlike SetPeerConfig, lowering the capacity never refills the bucket.
+    fn tighten_rate_limiter(
+        rate_limiter: &mut Option<RateLimiter>,
+        params: &RateLimitParams,
+    ) -> Result<()> {
+        let current_time = RateLimiter::now()?;
+        let mut limiter = rate_limiter.clone().unwrap_or_default();
+        if let Some(limiter_type) = &params.limiter_type {
+            limiter.set_limiter_type(limiter_type.clone(), current_time)?;
+        }
+        if let Some(capacity) = params.capacity {
+            let tokens = if rate_limiter.is_some() {
+                limiter.refill(0, current_time);
+                std::cmp::min(limiter.tokens, capacity)
+            } else {
+                capacity
+            };
+            limiter.set_capacity(capacity, current_time);
+            limiter.tokens = tokens;
+        }
+        if let Some(refill_rate) = params.refill_per_second {
+            limiter.set_rate(refill_rate, current_time);
+        }
+        *rate_limiter = Some(limiter);
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum GuardParams {
+    Pause,
+    DisablePeer { remote_eid: u32 },
+    OutboundRateLimit { remote_eid: u32, params: RateLimitParams },
+    InboundRateLimit { remote_eid: u32, params: RateLimitParams },
+}
+
+impl GuardParams {
+    pub fn remote_eid(&self) -> Option<u32> {
+        match self {
+            GuardParams::Pause => None,
+            GuardParams::DisablePeer { remote_eid }
+            | GuardParams::OutboundRateLimit { remote_eid, .. }
+            | GuardParams::InboundRateLimit { remote_eid, .. } => Some(*remote_eid),
+        }
+    }
+}
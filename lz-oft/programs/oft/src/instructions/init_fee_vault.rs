@@ -0,0 +1,56 @@
+use crate::*;
+use anchor_lang::system_program;
+
+/// Anyone can fund the fee vault of an OFT created before init_oft funded it. The native fees
+/// deposited on send are usually below the rent-exempt minimum, so they can only be deposited into
+/// a vault that already holds it.
+#[derive(Accounts)]
+pub struct InitFeeVault<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitFeeVault<'_> {
+    pub fn apply(ctx: &mut Context<InitFeeVault>) -> Result<()> {
+        fund_fee_vault(&ctx.accounts.payer, &ctx.accounts.fee_vault, &ctx.accounts.system_program)
+    }
+}
+
+// Shared with init_oft and init_native_oft
+pub(crate) fn fund_fee_vault<'info>(
+    payer: &Signer<'info>,
+    fee_vault: &SystemAccount<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let lamports = fee_vault_shortfall(fee_vault.lamports(), &Rent::get()?);
+    if lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: fee_vault.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns the lamports the fee vault lacks to be rent exempt.
+pub fn fee_vault_shortfall(fee_vault_lamports: u64, rent: &Rent) -> u64 {
+    rent.minimum_balance(0).saturating_sub(fee_vault_lamports)
+}
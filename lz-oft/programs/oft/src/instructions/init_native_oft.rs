@@ -0,0 +1,256 @@
+use crate::*;
+use anchor_lang::{
+    solana_program::{self, program_pack::Pack},
+    system_program,
+};
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        self,
+        extension::{metadata_pointer, ExtensionType},
+        instruction::AuthorityType,
+        state::{Account as AccountState, Mint as MintState, Multisig},
+    },
+    token_interface::Token2022,
+};
+use oapp::endpoint::{instructions::RegisterOAppParams, ID as ENDPOINT_ID};
+use spl_token_metadata_interface::state::TokenMetadata;
+
+/// Creates the Token-2022 mint of a native OFT, with its metadata in the mint itself, and the
+/// OFT in one go. The mint authority is the oft_store, or a 1-of-n multisig including it.
+#[derive(Accounts)]
+#[instruction(params: InitNativeOFTParams)]
+pub struct InitNativeOFT<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OFTStore::INIT_SPACE,
+        seeds = [OFT_SEED, token_escrow.key().as_ref()],
+        bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LzReceiveTypesAccounts::INIT_SPACE,
+        seeds = [LZ_RECEIVE_TYPES_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub lz_receive_types_accounts: Account<'info, LzReceiveTypesAccounts>,
+    /// funded with the rent-exempt minimum, so that it can take native fees of any amount
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    /// CHECK: the update authority of the metadata
+    #[account(address = params.admin)]
+    pub admin: AccountInfo<'info>,
+    /// the new mint
+    #[account(mut)]
+    pub token_mint: Signer<'info>,
+    /// the new escrow, owned by the oft_store
+    #[account(mut)]
+    pub token_escrow: Signer<'info>,
+    /// the new multisig, required if params.multisig_signers is set
+    #[account(mut)]
+    pub multisig: Option<Signer<'info>>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitNativeOFT<'info> {
+    pub fn apply(
+        ctx: &mut Context<'_, '_, '_, 'info, InitNativeOFT<'info>>,
+        params: &InitNativeOFTParams,
+    ) -> Result<()> {
+        let oft_store_key = ctx.accounts.oft_store.key();
+        let token_mint_key = ctx.accounts.token_mint.key();
+        let token_escrow_key = ctx.accounts.token_escrow.key();
+        let seeds: &[&[u8]] = &[OFT_SEED, token_escrow_key.as_ref(), &[ctx.bumps.oft_store]];
+        // the accounts of the other multisig signers lead the remaining accounts
+        let signers_len = params.multisig_signers.as_ref().map_or(0, |signers| signers.len());
+        require!(ctx.remaining_accounts.len() >= signers_len, OFTError::InvalidMintAuthority);
+        let (signer_accounts, endpoint_accounts) = ctx.remaining_accounts.split_at(signers_len);
+
+        // Create the mint, the metadata is appended to it by the token program
+        let token_metadata = TokenMetadata {
+            update_authority: Some(params.admin).try_into()?,
+            mint: token_mint_key,
+            name: params.name.clone(),
+            symbol: params.symbol.clone(),
+            uri: params.uri.clone(),
+            additional_metadata: vec![],
+        };
+        let mint_len = ExtensionType::try_calculate_account_len::<MintState>(&[
+            ExtensionType::MetadataPointer,
+        ])?;
+        ctx.accounts.create_account(
+            &ctx.accounts.token_mint,
+            mint_len,
+            token_metadata.tlv_size_of()?,
+        )?;
+        solana_program::program::invoke(
+            &metadata_pointer::instruction::initialize(
+                &spl_token_2022::ID,
+                &token_mint_key,
+                Some(params.admin),
+                Some(token_mint_key),
+            )?,
+            &[ctx.accounts.token_mint.to_account_info()],
+        )?;
+        solana_program::program::invoke(
+            &spl_token_2022::instruction::initialize_mint2(
+                &spl_token_2022::ID,
+                &token_mint_key,
+                &oft_store_key,
+                None,
+                params.decimals,
+            )?,
+            &[ctx.accounts.token_mint.to_account_info()],
+        )?;
+        solana_program::program::invoke_signed(
+            &spl_token_metadata_interface::instruction::initialize(
+                &spl_token_2022::ID,
+                &token_mint_key,
+                &params.admin,
+                &token_mint_key,
+                &oft_store_key,
+                token_metadata.name,
+                token_metadata.symbol,
+                token_metadata.uri,
+            ),
+            &[
+                ctx.accounts.token_mint.to_account_info(),
+                ctx.accounts.admin.to_account_info(),
+                ctx.accounts.oft_store.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        // Hand the mint authority over to a 1-of-n multisig of the oft_store and the signers
+        if let Some(signers) = &params.multisig_signers {
+            let multisig = ctx.accounts.multisig.as_ref().ok_or(OFTError::InvalidMintAuthority)?;
+            require!(
+                signers
+                    .iter()
+                    .zip(signer_accounts)
+                    .all(|(signer, account)| *signer == account.key()),
+                OFTError::InvalidMintAuthority
+            );
+            ctx.accounts.create_account(multisig, Multisig::LEN, 0)?;
+            let mut multisig_signers = vec![&oft_store_key];
+            multisig_signers.extend(signers);
+            let mut multisig_infos =
+                vec![multisig.to_account_info(), ctx.accounts.oft_store.to_account_info()];
+            multisig_infos.extend_from_slice(signer_accounts);
+            solana_program::program::invoke(
+                &spl_token_2022::instruction::initialize_multisig2(
+                    &spl_token_2022::ID,
+                    &multisig.key(),
+                    &multisig_signers,
+                    1,
+                )?,
+                &multisig_infos,
+            )?;
+            solana_program::program::invoke_signed(
+                &spl_token_2022::instruction::set_authority(
+                    &spl_token_2022::ID,
+                    &token_mint_key,
+                    Some(&multisig.key()),
+                    AuthorityType::MintTokens,
+                    &oft_store_key,
+                    &[],
+                )?,
+                &[
+                    ctx.accounts.token_mint.to_account_info(),
+                    ctx.accounts.oft_store.to_account_info(),
+                ],
+                &[seeds],
+            )?;
+        }
+
+        // Create the escrow
+        ctx.accounts.create_account(
+            &ctx.accounts.token_escrow,
+            ExtensionType::try_calculate_account_len::<AccountState>(&[])?,
+            0,
+        )?;
+        solana_program::program::invoke(
+            &spl_token_2022::instruction::initialize_account3(
+                &spl_token_2022::ID,
+                &token_escrow_key,
+                &token_mint_key,
+                &oft_store_key,
+            )?,
+            &[
+                ctx.accounts.token_escrow.to_account_info(),
+                ctx.accounts.token_mint.to_account_info(),
+            ],
+        )?;
+
+        // Initialize the oft_store
+        let oft_params = InitOFTParams {
+            oft_type: OFTType::Native,
+            admin: params.admin,
+            shared_decimals: params.shared_decimals,
+            endpoint_program: params.endpoint_program,
+        };
+        init_oft_store(
+            &mut ctx.accounts.oft_store,
+            &oft_params,
+            token_mint_key,
+            params.decimals,
+            token_escrow_key,
+            ctx.bumps.oft_store,
+        )?;
+
+        // Initialize the lz_receive_types_accounts
+        ctx.accounts.lz_receive_types_accounts.oft_store = oft_store_key;
+        ctx.accounts.lz_receive_types_accounts.token_mint = token_mint_key;
+        ctx.accounts.lz_receive_types_accounts.extra_account_metas = ID;
+
+        fund_fee_vault(&ctx.accounts.payer, &ctx.accounts.fee_vault, &ctx.accounts.system_program)?;
+
+        // Register the oapp
+        oapp::endpoint_cpi::register_oapp(
+            params.endpoint_program.unwrap_or(ENDPOINT_ID),
+            oft_store_key,
+            endpoint_accounts,
+            seeds,
+            RegisterOAppParams { delegate: params.admin },
+        )
+    }
+
+    // Creates an account owned by the token program, funded for extra_len more bytes of data.
+    fn create_account(&self, account: &Signer<'info>, len: usize, extra_len: usize) -> Result<()> {
+        system_program::create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: self.payer.to_account_info(),
+                    to: account.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(len + extra_len),
+            len as u64,
+            &spl_token_2022::ID,
+        )
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InitNativeOFTParams {
+    pub admin: Pubkey,
+    pub shared_decimals: u8,
+    pub decimals: u8,
+    pub endpoint_program: Option<Pubkey>,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    // if set, the mint authority is a 1-of-n multisig of the oft_store and these signers,
+    // whose accounts lead the remaining accounts
+    pub multisig_signers: Option<Vec<Pubkey>>,
+}
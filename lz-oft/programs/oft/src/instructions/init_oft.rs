@@ -22,6 +22,13 @@ pub struct InitOFT<'info> {
         bump
     )]
     pub lz_receive_types_accounts: Account<'info, LzReceiveTypesAccounts>,
+    /// funded with the rent-exempt minimum, so that it can take native fees of any amount
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
     #[account(mint::token_program = token_program)]
     pub token_mint: InterfaceAccount<'info, Mint>,
     #[account(
@@ -39,32 +46,22 @@ pub struct InitOFT<'info> {
 impl InitOFT<'_> {
     pub fn apply(ctx: &mut Context<InitOFT>, params: &InitOFTParams) -> Result<()> {
         // Initialize the oft_store
-        ctx.accounts.oft_store.oft_type = params.oft_type.clone();
-        require!(
-            ctx.accounts.token_mint.decimals >= params.shared_decimals,
-            OFTError::InvalidDecimals
-        );
-        ctx.accounts.oft_store.ld2sd_rate =
-            10u64.pow((ctx.accounts.token_mint.decimals - params.shared_decimals) as u32);
-        ctx.accounts.oft_store.token_mint = ctx.accounts.token_mint.key();
-        ctx.accounts.oft_store.token_escrow = ctx.accounts.token_escrow.key();
-        ctx.accounts.oft_store.endpoint_program =
-            if let Some(endpoint_program) = params.endpoint_program {
-                endpoint_program
-            } else {
-                ENDPOINT_ID
-            };
-        ctx.accounts.oft_store.bump = ctx.bumps.oft_store;
-        ctx.accounts.oft_store.tvl_ld = 0;
-        ctx.accounts.oft_store.admin = params.admin;
-        ctx.accounts.oft_store.default_fee_bps = 0;
-        ctx.accounts.oft_store.paused = false;
-        ctx.accounts.oft_store.pauser = None;
-        ctx.accounts.oft_store.unpauser = None;
+        init_oft_store(
+            &mut ctx.accounts.oft_store,
+            params,
+            ctx.accounts.token_mint.key(),
+            ctx.accounts.token_mint.decimals,
+            ctx.accounts.token_escrow.key(),
+            ctx.bumps.oft_store,
+        )?;
 
         // Initialize the lz_receive_types_accounts
         ctx.accounts.lz_receive_types_accounts.oft_store = ctx.accounts.oft_store.key();
         ctx.accounts.lz_receive_types_accounts.token_mint = ctx.accounts.token_mint.key();
+        ctx.accounts.lz_receive_types_accounts.extra_account_metas =
+            transfer_hook::get_extra_account_metas(&ctx.accounts.token_mint.to_account_info())?;
+
+        fund_fee_vault(&ctx.accounts.payer, &ctx.accounts.fee_vault, &ctx.accounts.system_program)?;
 
         // Register the oapp
         oapp::endpoint_cpi::register_oapp(
@@ -77,6 +74,47 @@ impl InitOFT<'_> {
     }
 }
 
+// Shared with init_native_oft
+pub(crate) fn init_oft_store(
+    oft_store: &mut OFTStore,
+    params: &InitOFTParams,
+    token_mint: Pubkey,
+    decimals: u8,
+    token_escrow: Pubkey,
+    bump: u8,
+) -> Result<()> {
+    oft_store.version = ACCOUNT_VERSION;
+    oft_store.oft_type = params.oft_type.clone();
+    require!(decimals >= params.shared_decimals, OFTError::InvalidDecimals);
+    oft_store.ld2sd_rate = 10u64.pow((decimals - params.shared_decimals) as u32);
+    oft_store.token_mint = token_mint;
+    oft_store.token_escrow = token_escrow;
+    oft_store.escrow_seed = token_escrow;
+    oft_store.endpoint_program = if let Some(endpoint_program) = params.endpoint_program {
+        endpoint_program
+    } else {
+        ENDPOINT_ID
+    };
+    oft_store.bump = bump;
+    oft_store.tvl_ld = 0;
+    oft_store.total_fee_ld = 0;
+    oft_store.admin = params.admin;
+    oft_store.default_fee_bps = 0;
+    oft_store.max_referral_fee_bps = 0;
+    oft_store.default_oft_limits = OFTLimits::default();
+    oft_store.paused = false;
+    oft_store.paused_until = None;
+    oft_store.pauser = None;
+    oft_store.unpauser = None;
+    oft_store.guardian = None;
+    oft_store.timelock_delay = 0;
+    oft_store.change_nonce = 0;
+    oft_store.outbound_rate_limiter = None;
+    oft_store.inbound_rate_limiter = None;
+    oft_store.native_sol = false;
+    Ok(())
+}
+
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct InitOFTParams {
     pub oft_type: OFTType,
@@ -0,0 +1,55 @@
+use crate::*;
+
+/// Creates the bucket of a sender. Anyone can pay for it, and it starts full.
+#[derive(Accounts)]
+#[instruction(params: InitSenderRateLimitParams)]
+pub struct InitSenderRateLimit<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SenderRateLimit::INIT_SPACE,
+        seeds = [SENDER_RATE_LIMIT_SEED, peer.key().as_ref(), params.sender.as_ref()],
+        bump
+    )]
+    pub sender_rate_limit: Account<'info, SenderRateLimit>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitSenderRateLimit<'_> {
+    pub fn apply(
+        ctx: &mut Context<InitSenderRateLimit>,
+        _params: &InitSenderRateLimitParams,
+    ) -> Result<()> {
+        let config = ctx
+            .accounts
+            .peer
+            .sender_rate_limit
+            .as_ref()
+            .ok_or(OFTError::InvalidRateLimiter)?;
+        ctx.accounts.sender_rate_limit.rate_limiter = config.new_rate_limiter(RateLimiter::now()?);
+        ctx.accounts.sender_rate_limit.bump = ctx.bumps.sender_rate_limit;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InitSenderRateLimitParams {
+    pub remote_eid: u32,
+    pub sender: Pubkey,
+}
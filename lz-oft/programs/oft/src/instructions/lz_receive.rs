@@ -1,14 +1,21 @@
 use crate::*;
-use anchor_lang::solana_program;
+use anchor_lang::{solana_program, system_program};
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token_2022::spl_token_2022::{self, solana_program::program_option::COption},
-    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+    associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken},
+    token_2022::spl_token_2022::{
+        self,
+        solana_program::{program_option::COption, program_pack::Pack},
+        state::Account as TokenAccountState,
+    },
+    token_interface::{
+        self, CloseAccount, InitializeAccount3, Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
 };
 use oapp::endpoint::{
     cpi::accounts::Clear,
     instructions::{ClearParams, SendComposeParams},
-    ConstructCPIContext,
+    state::PayloadHash,
+    ConstructCPIContext, PAYLOAD_HASH_SEED,
 };
 
 #[event_cpi]
@@ -30,7 +37,7 @@ pub struct LzReceive<'info> {
     pub peer: Account<'info, PeerConfig>,
     #[account(
         mut,
-        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
         bump = oft_store.bump
     )]
     pub oft_store: Account<'info, OFTStore>,
@@ -42,17 +49,15 @@ pub struct LzReceive<'info> {
         token::token_program = token_program
     )]
     pub token_escrow: InterfaceAccount<'info, TokenAccount>,
-    /// CHECK: the wallet address to receive the token
+    /// CHECK: the wallet address to receive the token, or the token account itself if the message
+    /// is flagged with FLAG_TO_TOKEN_ACCOUNT. It is writable in native SOL mode to receive lamports
     #[account(address = Pubkey::from(msg_codec::send_to(&params.message)) @OFTError::InvalidTokenDest)]
     pub to_address: AccountInfo<'info>,
-    #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = token_mint,
-        associated_token::authority = to_address,
-        associated_token::token_program = token_program
-    )]
-    pub token_dest: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: the associated token account of to_address, which is created if needed, or
+    /// to_address itself. It is validated in init_token_dest, or in init_unwrap_account for the
+    /// temporary account of native SOL mode
+    #[account(mut)]
+    pub token_dest: AccountInfo<'info>,
     #[account(
         mut,
         address = oft_store.token_mint,
@@ -67,17 +72,76 @@ pub struct LzReceive<'info> {
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    // Only used to reimburse the rent of a new ATA when the peer has an ata_rent_fee_ld
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub rent_vault: Option<SystemAccount<'info>>,
+    /// CHECK: only used in native SOL mode, the wSOL ATA of to_address. It receives the tokens if
+    /// the amount is too small to fund to_address with lamports. Validated in unwrap_sol
+    #[account(mut)]
+    pub wsol_dest: Option<AccountInfo<'info>>,
 }
 
-impl LzReceive<'_> {
-    pub fn apply(ctx: &mut Context<LzReceive>, params: &LzReceiveParams) -> Result<()> {
-        require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+impl<'info> LzReceive<'info> {
+    pub fn apply(
+        ctx: &mut Context<'_, '_, '_, 'info, LzReceive<'info>>,
+        params: &LzReceiveParams,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(!ctx.accounts.oft_store.is_paused(now), OFTError::Paused);
+        require!(!ctx.accounts.peer.is_inbound_paused(now), OFTError::Paused);
+
+        // Only the escrow of the adapter transfers tokens, so only it leads with the hook accounts
+        let (hook_accounts, remaining_accounts) =
+            if ctx.accounts.oft_store.oft_type == OFTType::Adapter {
+                transfer_hook::split_accounts(
+                    &ctx.accounts.token_mint.to_account_info(),
+                    ctx.remaining_accounts,
+                )?
+            } else {
+                (&[][..], ctx.remaining_accounts)
+            };
+
+        // Convert the amount from sd to ld
+        let amount_sd = msg_codec::amount_sd(&params.message);
+        let mut amount_received_ld = ctx.accounts.oft_store.sd2ld(amount_sd);
 
-        let oft_store_seed = ctx.accounts.token_escrow.key();
+        // Trip the circuit breaker on abnormal inbound volume. The payload is not cleared, so the
+        // message stays parked at the endpoint and can be retried once the unpauser approves it.
+        if let Some(circuit_breaker) = ctx.accounts.peer.inbound_circuit_breaker.as_mut() {
+            if !circuit_breaker.try_record(amount_received_ld, &params.guid, RateLimiter::now()?) {
+                // only a verified payload may trip the breaker, otherwise anyone could pause the peer
+                require!(
+                    is_payload_verified(
+                        ctx.accounts.oft_store.endpoint_program,
+                        ctx.accounts.oft_store.key(),
+                        params,
+                        remaining_accounts,
+                    ),
+                    OFTError::InvalidPayload
+                );
+                let (volume, threshold) = (circuit_breaker.volume, circuit_breaker.threshold);
+                ctx.accounts.peer.inbound_paused = true;
+                ctx.accounts.peer.inbound_paused_until = None;
+                emit_cpi!(OFTCircuitTripped {
+                    guid: params.guid,
+                    src_eid: params.src_eid,
+                    amount_received_ld,
+                    volume,
+                    threshold,
+                });
+                return Ok(());
+            }
+        }
+
+        let oft_store_seed = ctx.accounts.oft_store.escrow_seed;
         let seeds: &[&[u8]] = &[OFT_SEED, oft_store_seed.as_ref(), &[ctx.accounts.oft_store.bump]];
 
         // Validate and clear the payload
-        let accounts_for_clear = &ctx.remaining_accounts[0..Clear::MIN_ACCOUNTS_LEN];
+        let accounts_for_clear = &remaining_accounts[0..Clear::MIN_ACCOUNTS_LEN];
         let _ = oapp::endpoint_cpi::clear(
             ctx.accounts.oft_store.endpoint_program,
             ctx.accounts.oft_store.key(),
@@ -93,23 +157,52 @@ impl LzReceive<'_> {
             },
         )?;
 
-        // Convert the amount from sd to ld
-        let amount_sd = msg_codec::amount_sd(&params.message);
-        let mut amount_received_ld = ctx.accounts.oft_store.sd2ld(amount_sd);
+        let to_token_account = msg_codec::is_to_token_account(&params.message);
+        // in native SOL mode the tokens are unwrapped through token_dest, instead of an ATA
+        let unwrap_sol = ctx.accounts.oft_store.native_sol && !to_token_account;
+        let token_dest_created = if unwrap_sol {
+            Self::init_unwrap_account(ctx)?;
+            false
+        } else {
+            Self::init_token_dest(ctx, to_token_account)?
+        };
+        // Composed messages are exempt, lz_receive_types can not predict the amount for the compose.
+        let mut ata_rent_fee_ld =
+            if token_dest_created && msg_codec::compose_msg(&params.message).is_none() {
+                ctx.accounts.peer.get_ata_rent_fee_ld(amount_received_ld)
+            } else {
+                0
+            };
+        // the recipient is only charged if the payer gets the rent back
+        if ata_rent_fee_ld > 0 && !Self::reimburse_rent(ctx)? {
+            ata_rent_fee_ld = 0;
+        }
+        // the deducted tokens stay in the escrow as fees, which the admin uses to refill the vault
+        let amount_to_dest_ld = amount_received_ld - ata_rent_fee_ld;
+        ctx.accounts.oft_store.total_fee_ld += ata_rent_fee_ld;
 
         // Consume the inbound rate limiter
+        let current_time = RateLimiter::now()?;
         if let Some(rate_limiter) = ctx.accounts.peer.inbound_rate_limiter.as_mut() {
-            rate_limiter.try_consume(amount_received_ld)?;
+            rate_limiter.try_consume(amount_received_ld, current_time)?;
         }
         // Refill the outbound rate limiter
         if let Some(rate_limiter) = ctx.accounts.peer.outbound_rate_limiter.as_mut() {
-            rate_limiter.refill(amount_received_ld)?;
+            rate_limiter.refill(amount_received_ld, current_time);
+        }
+        // Same for the global rate limiters
+        if let Some(rate_limiter) = ctx.accounts.oft_store.inbound_rate_limiter.as_mut() {
+            rate_limiter.try_consume(amount_received_ld, current_time)?;
+        }
+        if let Some(rate_limiter) = ctx.accounts.oft_store.outbound_rate_limiter.as_mut() {
+            rate_limiter.refill(amount_received_ld, current_time);
         }
+        ctx.accounts.peer.stats.record_received(amount_to_dest_ld);
 
         if ctx.accounts.oft_store.oft_type == OFTType::Adapter {
             // unlock from escrow
             ctx.accounts.oft_store.tvl_ld -= amount_received_ld;
-            token_interface::transfer_checked(
+            transfer_hook::transfer_checked(
                 CpiContext::new(
                     ctx.accounts.token_program.to_account_info(),
                     TransferChecked {
@@ -120,34 +213,47 @@ impl LzReceive<'_> {
                     },
                 )
                 .with_signer(&[&seeds]),
-                amount_received_ld,
+                amount_to_dest_ld,
                 ctx.accounts.token_mint.decimals,
+                hook_accounts,
             )?;
 
             // update the amount_received_ld with the post transfer fee amount
             amount_received_ld =
-                get_post_fee_amount_ld(&ctx.accounts.token_mint, amount_received_ld)?
+                get_post_fee_amount_ld(&ctx.accounts.token_mint, amount_to_dest_ld)?;
+            if unwrap_sol {
+                Self::unwrap_sol(ctx, amount_received_ld, seeds)?;
+            }
         } else if let Some(mint_authority) = &ctx.accounts.mint_authority {
             // Native type
             // mint
-            let ix = spl_token_2022::instruction::mint_to(
-                ctx.accounts.token_program.key,
-                &ctx.accounts.token_mint.key(),
-                &ctx.accounts.token_dest.key(),
-                mint_authority.key,
-                &[&ctx.accounts.oft_store.key()],
-                amount_received_ld,
-            )?;
-            solana_program::program::invoke_signed(
-                &ix,
-                &[
-                    ctx.accounts.token_dest.to_account_info(),
-                    ctx.accounts.token_mint.to_account_info(),
-                    mint_authority.to_account_info(),
-                    ctx.accounts.oft_store.to_account_info(),
-                ],
-                &[&seeds],
-            )?;
+            let escrow_info = ctx.accounts.token_escrow.to_account_info();
+            for (dest, amount_ld) in
+                [(&ctx.accounts.token_dest, amount_to_dest_ld), (&escrow_info, ata_rent_fee_ld)]
+            {
+                if amount_ld == 0 {
+                    continue;
+                }
+                let ix = spl_token_2022::instruction::mint_to(
+                    ctx.accounts.token_program.key,
+                    &ctx.accounts.token_mint.key(),
+                    &dest.key(),
+                    mint_authority.key,
+                    &[&ctx.accounts.oft_store.key()],
+                    amount_ld,
+                )?;
+                solana_program::program::invoke_signed(
+                    &ix,
+                    &[
+                        dest.to_account_info(),
+                        ctx.accounts.token_mint.to_account_info(),
+                        mint_authority.to_account_info(),
+                        ctx.accounts.oft_store.to_account_info(),
+                    ],
+                    &[&seeds],
+                )?;
+            }
+            amount_received_ld = amount_to_dest_ld;
         } else {
             return Err(OFTError::InvalidMintAuthority.into());
         }
@@ -156,7 +262,7 @@ impl LzReceive<'_> {
             oapp::endpoint_cpi::send_compose(
                 ctx.accounts.oft_store.endpoint_program,
                 ctx.accounts.oft_store.key(),
-                &ctx.remaining_accounts[Clear::MIN_ACCOUNTS_LEN..],
+                &remaining_accounts[Clear::MIN_ACCOUNTS_LEN..],
                 seeds,
                 SendComposeParams {
                     to: ctx.accounts.to_address.key(),
@@ -177,7 +283,269 @@ impl LzReceive<'_> {
             src_eid: params.src_eid,
             to: ctx.accounts.to_address.key(),
             amount_received_ld,
+            ata_rent_fee_ld,
         });
         Ok(())
     }
+
+    /// Returns whether the associated token account had to be created.
+    fn init_token_dest(ctx: &Context<LzReceive>, to_token_account: bool) -> Result<bool> {
+        let token_dest = &ctx.accounts.token_dest;
+        let mut created = false;
+        if to_token_account {
+            require_keys_eq!(
+                token_dest.key(),
+                ctx.accounts.to_address.key(),
+                OFTError::InvalidTokenDest
+            );
+        } else {
+            let associated_token_account = get_associated_token_address_with_program_id(
+                &ctx.accounts.to_address.key(),
+                &ctx.accounts.token_mint.key(),
+                &ctx.accounts.token_program.key(),
+            );
+            require_keys_eq!(
+                token_dest.key(),
+                associated_token_account,
+                OFTError::InvalidTokenDest
+            );
+            // only the associated token program can create an account at this address
+            created = token_dest.data_is_empty();
+            if created {
+                associated_token::create(CpiContext::new(
+                    ctx.accounts.associated_token_program.to_account_info(),
+                    associated_token::Create {
+                        payer: ctx.accounts.payer.to_account_info(),
+                        associated_token: token_dest.to_account_info(),
+                        authority: ctx.accounts.to_address.to_account_info(),
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        token_program: ctx.accounts.token_program.to_account_info(),
+                    },
+                ))?;
+            }
+        }
+
+        require_keys_eq!(
+            *token_dest.owner,
+            ctx.accounts.token_program.key(),
+            OFTError::InvalidTokenDest
+        );
+        let token_account = TokenAccount::try_deserialize(&mut &token_dest.try_borrow_data()?[..])?;
+        require_keys_eq!(
+            token_account.mint,
+            ctx.accounts.token_mint.key(),
+            OFTError::InvalidTokenDest
+        );
+        // the owner of an existing ATA may have been reassigned
+        if !to_token_account {
+            require_keys_eq!(
+                token_account.owner,
+                ctx.accounts.to_address.key(),
+                OFTError::InvalidTokenDest
+            );
+        }
+        Ok(created)
+    }
+
+    // Creates the temporary token account to unwrap SOL through, at the NATIVE_SOL_SEED PDA.
+    fn init_unwrap_account(ctx: &Context<LzReceive>) -> Result<()> {
+        let oft_store_key = ctx.accounts.oft_store.key();
+        let (unwrap_account, bump) = Pubkey::find_program_address(
+            &[NATIVE_SOL_SEED, oft_store_key.as_ref()],
+            ctx.program_id,
+        );
+        let token_dest = &ctx.accounts.token_dest;
+        require_keys_eq!(token_dest.key(), unwrap_account, OFTError::InvalidTokenDest);
+        let seeds: &[&[u8]] = &[NATIVE_SOL_SEED, oft_store_key.as_ref(), &[bump]];
+
+        // fund, allocate and assign rather than create_account, which anyone could block by
+        // sending lamports to the address beforehand
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let lamports = Rent::get()?
+            .minimum_balance(TokenAccountState::LEN)
+            .saturating_sub(token_dest.lamports());
+        if lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: token_dest.to_account_info(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Allocate { account_to_allocate: token_dest.to_account_info() },
+            )
+            .with_signer(&[seeds]),
+            TokenAccountState::LEN as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new(
+                system_program,
+                system_program::Assign { account_to_assign: token_dest.to_account_info() },
+            )
+            .with_signer(&[seeds]),
+            ctx.accounts.token_program.key,
+        )?;
+        token_interface::initialize_account3(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            InitializeAccount3 {
+                account: token_dest.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                authority: ctx.accounts.oft_store.to_account_info(),
+            },
+        ))
+    }
+
+    // Closes the temporary token account into the payer, who forwards the lamports to to_address.
+    // A new account can not take less than the rent-exempt minimum, so such an amount is delivered
+    // as wSOL to the ATA of to_address instead, rather than blocking the message for good.
+    fn unwrap_sol(ctx: &Context<LzReceive>, amount_ld: u64, seeds: &[&[u8]]) -> Result<()> {
+        let to_address = &ctx.accounts.to_address;
+        let to_wsol = !can_receive_lamports(
+            to_address.lamports(),
+            to_address.data_len(),
+            amount_ld,
+            &Rent::get()?,
+        );
+        if to_wsol {
+            Self::deliver_wsol(ctx, amount_ld, seeds)?;
+        }
+        token_interface::close_account(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: ctx.accounts.token_dest.to_account_info(),
+                    destination: ctx.accounts.payer.to_account_info(),
+                    authority: ctx.accounts.oft_store.to_account_info(),
+                },
+            )
+            .with_signer(&[seeds]),
+        )?;
+        if to_wsol {
+            return Ok(());
+        }
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: to_address.to_account_info(),
+                },
+            ),
+            amount_ld,
+        )
+    }
+
+    // Moves the tokens of the temporary account to the wSOL ATA of to_address, created if needed.
+    fn deliver_wsol(ctx: &Context<LzReceive>, amount_ld: u64, seeds: &[&[u8]]) -> Result<()> {
+        let wsol_dest = ctx.accounts.wsol_dest.as_ref().ok_or(OFTError::InvalidTokenDest)?;
+        require_keys_eq!(
+            wsol_dest.key(),
+            get_associated_token_address_with_program_id(
+                &ctx.accounts.to_address.key(),
+                &ctx.accounts.token_mint.key(),
+                &ctx.accounts.token_program.key(),
+            ),
+            OFTError::InvalidTokenDest
+        );
+        associated_token::create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: wsol_dest.to_account_info(),
+                authority: ctx.accounts.to_address.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_dest.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: wsol_dest.to_account_info(),
+                    authority: ctx.accounts.oft_store.to_account_info(),
+                },
+            )
+            .with_signer(&[seeds]),
+            amount_ld,
+            ctx.accounts.token_mint.decimals,
+        )
+    }
+
+    // Pays back the rent of the new ATA to the payer from the rent vault. Returns false, and pays
+    // nothing, if the vault can not cover it in full.
+    fn reimburse_rent(ctx: &Context<LzReceive>) -> Result<bool> {
+        let Some(rent_vault) = &ctx.accounts.rent_vault else {
+            return Ok(false);
+        };
+        let lamports = ctx.accounts.token_dest.lamports();
+        if rent_vault_spare_lamports(rent_vault.lamports(), &Rent::get()?) < lamports {
+            return Ok(false);
+        }
+        let oft_store_key = ctx.accounts.oft_store.key();
+        let seeds: &[&[u8]] = &[RENT_VAULT_SEED, oft_store_key.as_ref(), &[ctx.bumps.rent_vault]];
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: rent_vault.to_account_info(),
+                    to: ctx.accounts.payer.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+            lamports,
+        )?;
+        Ok(true)
+    }
+}
+
+/// Whether an account can take the lamports unwrapped for it, and stay rent exempt.
+pub fn can_receive_lamports(lamports: u64, data_len: usize, amount_ld: u64, rent: &Rent) -> bool {
+    lamports.saturating_add(amount_ld) >= rent.minimum_balance(data_len)
+}
+
+// Checks that the endpoint holds the payload hash of the message, i.e. that `clear` would succeed.
+fn is_payload_verified(
+    endpoint_program: Pubkey,
+    receiver: Pubkey,
+    params: &LzReceiveParams,
+    accounts: &[AccountInfo],
+) -> bool {
+    let (payload_hash_account, _) = Pubkey::find_program_address(
+        &[
+            PAYLOAD_HASH_SEED,
+            receiver.as_ref(),
+            &params.src_eid.to_be_bytes(),
+            &params.sender,
+            &params.nonce.to_be_bytes(),
+        ],
+        &endpoint_program,
+    );
+    let Some(account) = accounts.iter().find(|account| account.key() == payload_hash_account)
+    else {
+        return false;
+    };
+    if account.owner != &endpoint_program {
+        return false;
+    }
+    let Ok(data) = account.try_borrow_data() else {
+        return false;
+    };
+    match PayloadHash::try_deserialize(&mut &data[..]) {
+        Ok(payload_hash) => {
+            payload_hash.hash
+                == solana_program::keccak::hashv(&[&params.guid, &params.message]).to_bytes()
+        },
+        Err(_) => false,
+    }
 }
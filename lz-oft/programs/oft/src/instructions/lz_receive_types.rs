@@ -10,12 +10,14 @@ use oapp::endpoint_cpi::LzAccount;
 #[derive(Accounts)]
 pub struct LzReceiveTypes<'info> {
     #[account(
-        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
         bump = oft_store.bump
     )]
     pub oft_store: Account<'info, OFTStore>,
     #[account(address = oft_store.token_mint)]
     pub token_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: the extra account metas of the transfer hook, checked against the mint if it has one
+    pub extra_account_metas: AccountInfo<'info>,
 }
 
 // account structure
@@ -23,18 +25,22 @@ pub struct LzReceiveTypes<'info> {
 // account 1 - peer
 // account 2 - oft store
 // account 3 - token escrow
-// account 4 - to address / wallet address
-// account 5 - token dest
+// account 4 - to address / wallet address (writable in native SOL mode)
+// account 5 - token dest (the ATA of the to address, or the to address itself if it is a token account,
+//             or the temporary account to unwrap SOL through in native SOL mode)
 // account 6 - token mint
 // account 7 - mint authority (optional)
 // account 8 - token program
 // account 9 - associated token program
 // account 10 - system program
-// account 11 - event authority
-// account 12 - this program
+// account 11 - rent vault
+// account 12 - wsol dest (the wSOL ATA of the to address in native SOL mode, optional)
+// account 13 - event authority
+// account 14 - this program
 // account remaining accounts
-//      0..9 - accounts for clear
-//      9..16 - accounts for compose
+//      0..n - accounts for the transfer hook of the adapter, if the mint has one
+//      n..n+9 - accounts for clear
+//      n+9..n+16 - accounts for compose
 impl LzReceiveTypes<'_> {
     pub fn apply(
         ctx: &Context<LzReceiveTypes>,
@@ -60,11 +66,23 @@ impl LzReceiveTypes<'_> {
         // account 4..9
         let to_address = Pubkey::from(msg_codec::send_to(&params.message));
         let token_program = ctx.accounts.token_mint.to_account_info().owner;
-        let token_dest = get_associated_token_address_with_program_id(
-            &to_address,
-            &ctx.accounts.oft_store.token_mint,
-            token_program,
-        );
+        let to_token_account = msg_codec::is_to_token_account(&params.message);
+        let unwrap_sol = ctx.accounts.oft_store.native_sol && !to_token_account;
+        let token_dest = if to_token_account {
+            to_address
+        } else if unwrap_sol {
+            Pubkey::find_program_address(
+                &[NATIVE_SOL_SEED, ctx.accounts.oft_store.key().as_ref()],
+                ctx.program_id,
+            )
+            .0
+        } else {
+            get_associated_token_address_with_program_id(
+                &to_address,
+                &ctx.accounts.oft_store.token_mint,
+                token_program,
+            )
+        };
         let mint_authority =
             if let COption::Some(mint_authority) = ctx.accounts.token_mint.mint_authority {
                 mint_authority
@@ -72,19 +90,32 @@ impl LzReceiveTypes<'_> {
                 ctx.program_id.key()
             };
         accounts.extend_from_slice(&[
-            LzAccount { pubkey: to_address, is_signer: false, is_writable: false }, // 4
-            LzAccount { pubkey: token_dest, is_signer: false, is_writable: true },  // 5
+            LzAccount { pubkey: to_address, is_signer: false, is_writable: unwrap_sol }, // 4
+            LzAccount { pubkey: token_dest, is_signer: false, is_writable: true },       // 5
             LzAccount {
                 pubkey: ctx.accounts.token_mint.key(),
                 is_signer: false,
                 is_writable: true,
             }, // 6
-            LzAccount { pubkey: mint_authority, is_signer: false, is_writable: false }, // 7
-            LzAccount { pubkey: *token_program, is_signer: false, is_writable: false }, // 8
+            LzAccount { pubkey: mint_authority, is_signer: false, is_writable: false },  // 7
+            LzAccount { pubkey: *token_program, is_signer: false, is_writable: false },  // 8
             LzAccount { pubkey: ASSOCIATED_TOKEN_ID, is_signer: false, is_writable: false }, // 9
         ]);
 
-        // account 10..12
+        // account 10..14
+        let (rent_vault, _) = Pubkey::find_program_address(
+            &[RENT_VAULT_SEED, ctx.accounts.oft_store.key().as_ref()],
+            ctx.program_id,
+        );
+        let wsol_dest = if unwrap_sol {
+            get_associated_token_address_with_program_id(
+                &to_address,
+                &ctx.accounts.oft_store.token_mint,
+                token_program,
+            )
+        } else {
+            ctx.program_id.key()
+        };
         let (event_authority_account, _) =
             Pubkey::find_program_address(&[oapp::endpoint_cpi::EVENT_SEED], &ctx.program_id);
         accounts.extend_from_slice(&[
@@ -93,12 +124,28 @@ impl LzReceiveTypes<'_> {
                 is_signer: false,
                 is_writable: false,
             }, // 10
-            LzAccount { pubkey: event_authority_account, is_signer: false, is_writable: false }, // 11
-            LzAccount { pubkey: ctx.program_id.key(), is_signer: false, is_writable: false }, // 12
+            LzAccount { pubkey: rent_vault, is_signer: false, is_writable: true }, // 11
+            LzAccount { pubkey: wsol_dest, is_signer: false, is_writable: unwrap_sol }, // 12
+            LzAccount { pubkey: event_authority_account, is_signer: false, is_writable: false }, // 13
+            LzAccount { pubkey: ctx.program_id.key(), is_signer: false, is_writable: false }, // 14
         ]);
 
+        // remaining accounts 0..n
+        if ctx.accounts.oft_store.oft_type == OFTType::Adapter {
+            // the amount of the message, lz_receive transfers less if it deducts the ATA rent fee
+            let amount_ld = ctx.accounts.oft_store.sd2ld(msg_codec::amount_sd(&params.message));
+            accounts.extend(transfer_hook::get_lz_accounts(
+                &ctx.accounts.token_mint.to_account_info(),
+                &ctx.accounts.extra_account_metas,
+                ctx.accounts.oft_store.token_escrow,
+                token_dest,
+                ctx.accounts.oft_store.key(),
+                amount_ld,
+            )?);
+        }
+
         let endpoint_program = ctx.accounts.oft_store.endpoint_program;
-        // remaining accounts 0..9
+        // remaining accounts n..n+9
         let accounts_for_clear = oapp::endpoint_cpi::get_accounts_for_clear(
             endpoint_program,
             &ctx.accounts.oft_store.key(),
@@ -108,7 +155,7 @@ impl LzReceiveTypes<'_> {
         );
         accounts.extend(accounts_for_clear);
 
-        // remaining accounts 9..16
+        // remaining accounts n+9..n+16
         if let Some(message) = msg_codec::compose_msg(&params.message) {
             let amount_sd = msg_codec::amount_sd(&params.message);
             let amount_ld = ctx.accounts.oft_store.sd2ld(amount_sd);
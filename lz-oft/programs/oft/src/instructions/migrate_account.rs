@@ -0,0 +1,52 @@
+use crate::*;
+
+/// Anyone can migrate the oft_store, and the peer of params.remote_eid if passed, to the current
+/// ACCOUNT_VERSION. The accounts are reallocated at the payer's expense and rewritten in the current
+/// layout, with the fields added since their version at their defaults. Legacy accounts can be read
+/// but not written, so the oft_store and every peer have to be migrated before they are used to
+/// send, receive or update the config.
+#[derive(Accounts)]
+#[instruction(params: MigrateAccountParams)]
+pub struct MigrateAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        realloc = 8 + OFTStore::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.unwrap_or_default().to_be_bytes()
+        ],
+        bump = peer.bump,
+        realloc = 8 + PeerConfig::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub peer: Option<Account<'info, PeerConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateAccount<'_> {
+    pub fn apply(ctx: &mut Context<MigrateAccount>, _params: &MigrateAccountParams) -> Result<()> {
+        // the legacy layout has been read with the defaults, it is written in the current one on exit
+        ctx.accounts.oft_store.version = ACCOUNT_VERSION;
+        if let Some(peer) = ctx.accounts.peer.as_mut() {
+            peer.version = ACCOUNT_VERSION;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MigrateAccountParams {
+    pub remote_eid: Option<u32>, // required with the peer
+}
@@ -0,0 +1,112 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Moves the whole balance of the escrow, i.e. the tvl_ld and the accrued fees, into a new escrow
+/// owned by the oft_store. The oft_store address stays derived from the initial escrow.
+/// When the timelock is enabled, the migration has to be queued as ConfigChange::MigrateEscrow.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateEscrow<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        address = oft_store.token_escrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = new_token_escrow.key() != token_escrow.key() @OFTError::InvalidEscrow,
+        constraint = new_token_escrow.delegate.is_none()
+            && new_token_escrow.close_authority.is_none() @OFTError::InvalidEscrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub new_token_escrow: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// required when the timelock is enabled: the queued change, closed to its payer
+    #[account(
+        mut,
+        seeds = [
+            PENDING_CHANGE_SEED,
+            oft_store.key().as_ref(),
+            &pending_change.nonce.to_be_bytes()
+        ],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Option<Account<'info, PendingChange>>,
+    /// CHECK: receives the rent of the pending change, checked against it
+    #[account(mut)]
+    pub payer: Option<AccountInfo<'info>>,
+}
+
+impl<'info> MigrateEscrow<'info> {
+    pub fn apply(ctx: &mut Context<'_, '_, '_, 'info, MigrateEscrow<'info>>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.oft_store.is_paused(now), OFTError::NotPaused);
+        let tvl_ld = ctx.accounts.oft_store.tvl_ld;
+        require!(ctx.accounts.token_escrow.amount >= tvl_ld, OFTError::InsufficientEscrow);
+        PendingChange::consume(
+            &ctx.accounts.pending_change,
+            &ctx.accounts.payer,
+            &ctx.accounts.oft_store,
+            &ConfigChange::MigrateEscrow { new_token_escrow: ctx.accounts.new_token_escrow.key() },
+        )?;
+
+        // the remaining accounts are the transfer hook accounts, if any
+        let (hook_accounts, _) = transfer_hook::split_accounts(
+            &ctx.accounts.token_mint.to_account_info(),
+            ctx.remaining_accounts,
+        )?;
+        let seeds: &[&[u8]] = &[
+            OFT_SEED,
+            &ctx.accounts.oft_store.escrow_seed.to_bytes(),
+            &[ctx.accounts.oft_store.bump],
+        ];
+        let amount_ld = ctx.accounts.token_escrow.amount;
+        transfer_hook::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_escrow.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.new_token_escrow.to_account_info(),
+                    authority: ctx.accounts.oft_store.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+            amount_ld,
+            ctx.accounts.token_mint.decimals,
+            hook_accounts,
+        )?;
+
+        // a transfer fee must not leave the new escrow short of the tvl_ld
+        ctx.accounts.new_token_escrow.reload()?;
+        require!(ctx.accounts.new_token_escrow.amount >= tvl_ld, OFTError::InsufficientEscrow);
+
+        let old_escrow = ctx.accounts.token_escrow.key();
+        ctx.accounts.oft_store.token_escrow = ctx.accounts.new_token_escrow.key();
+        emit_cpi!(OFTEscrowMigrated {
+            old_escrow,
+            new_escrow: ctx.accounts.oft_store.token_escrow,
+            amount_ld,
+            tvl_ld,
+        });
+        Ok(())
+    }
+}
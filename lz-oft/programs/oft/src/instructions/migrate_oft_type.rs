@@ -0,0 +1,117 @@
+use crate::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::{
+    token_2022::spl_token_2022::{solana_program::program_option::COption, state::Multisig},
+    token_interface::{self, Burn, Mint, TokenAccount, TokenInterface},
+};
+
+/// Turns an adapter into the native OFT of its mint, once the oft_store has been given the mint
+/// authority. The locked tokens are burned, as they are minted again when they come back.
+/// When the timelock is enabled, the migration has to be queued as ConfigChange::MigrateOFTType.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateOFTType<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        address = oft_store.token_escrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: required if the mint authority is a multisig, validated in can_mint
+    pub mint_authority: Option<AccountInfo<'info>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// required when the timelock is enabled: the queued change, closed to its payer
+    #[account(
+        mut,
+        seeds = [
+            PENDING_CHANGE_SEED,
+            oft_store.key().as_ref(),
+            &pending_change.nonce.to_be_bytes()
+        ],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Option<Account<'info, PendingChange>>,
+    /// CHECK: receives the rent of the pending change, checked against it
+    #[account(mut)]
+    pub payer: Option<AccountInfo<'info>>,
+}
+
+impl MigrateOFTType<'_> {
+    pub fn apply(ctx: &mut Context<MigrateOFTType>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.oft_store.is_paused(now), OFTError::NotPaused);
+        require!(ctx.accounts.oft_store.oft_type == OFTType::Adapter, OFTError::InvalidOFTType);
+        PendingChange::consume(
+            &ctx.accounts.pending_change,
+            &ctx.accounts.payer,
+            &ctx.accounts.oft_store,
+            &ConfigChange::MigrateOFTType,
+        )?;
+        let tvl_ld = ctx.accounts.oft_store.tvl_ld;
+        require!(ctx.accounts.token_escrow.amount >= tvl_ld, OFTError::InsufficientEscrow);
+        require!(Self::can_mint(ctx)?, OFTError::InvalidMintAuthority);
+
+        let seeds: &[&[u8]] = &[
+            OFT_SEED,
+            &ctx.accounts.oft_store.escrow_seed.to_bytes(),
+            &[ctx.accounts.oft_store.bump],
+        ];
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.token_escrow.to_account_info(),
+                    authority: ctx.accounts.oft_store.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+            tvl_ld,
+        )?;
+
+        // the escrow only holds fees from now on
+        ctx.accounts.oft_store.tvl_ld = 0;
+        ctx.accounts.oft_store.oft_type = OFTType::Native;
+        ctx.accounts.oft_store.native_sol = false;
+        emit_cpi!(OFTTypeMigrated { oft_type: OFTType::Native, tvl_burned_ld: tvl_ld });
+        Ok(())
+    }
+
+    // Same requirements as the mint authority of LzReceive: the oft_store itself, or a 1-of-n
+    // multisig including it.
+    fn can_mint(ctx: &Context<MigrateOFTType>) -> Result<bool> {
+        let oft_store_key = ctx.accounts.oft_store.key();
+        let COption::Some(authority) = ctx.accounts.token_mint.mint_authority else {
+            return Ok(false);
+        };
+        if authority == oft_store_key {
+            return Ok(true);
+        }
+        let Some(mint_authority) = &ctx.accounts.mint_authority else {
+            return Ok(false);
+        };
+        if mint_authority.key() != authority
+            || *mint_authority.owner != ctx.accounts.token_program.key()
+        {
+            return Ok(false);
+        }
+        let multisig = Multisig::unpack(&mint_authority.try_borrow_data()?)?;
+        Ok(multisig.m == 1 && multisig.signers[..multisig.n as usize].contains(&oft_store_key))
+    }
+}
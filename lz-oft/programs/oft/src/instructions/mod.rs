@@ -1,21 +1,65 @@
+pub mod add_fee_exemption;
+pub mod cancel_change;
+pub mod distribute_fees;
+pub mod execute_change;
+pub mod guard;
+pub mod init_fee_vault;
+pub mod init_native_oft;
 pub mod init_oft;
+pub mod init_sender_rate_limit;
 pub mod lz_receive;
 pub mod lz_receive_types;
+pub mod migrate_account;
+pub mod migrate_escrow;
+pub mod migrate_oft_type;
+pub mod oft_status;
+pub mod pause_status;
+pub mod peer_stats;
+pub mod queue_change;
 pub mod quote_oft;
+pub mod quote_receive;
 pub mod quote_send;
+pub mod rate_limit_status;
+pub mod remove_fee_exemption;
 pub mod send;
+pub mod set_fee_distribution;
+pub mod set_lz_receive_types_accounts;
 pub mod set_oft_config;
 pub mod set_pause;
 pub mod set_peer_config;
 pub mod withdraw_fee;
+pub mod withdraw_native_fee;
+pub mod withdraw_rent_vault;
 
+pub use add_fee_exemption::*;
+pub use cancel_change::*;
+pub use distribute_fees::*;
+pub use execute_change::*;
+pub use guard::*;
+pub use init_fee_vault::*;
+pub use init_native_oft::*;
 pub use init_oft::*;
+pub use init_sender_rate_limit::*;
 pub use lz_receive::*;
 pub use lz_receive_types::*;
+pub use migrate_account::*;
+pub use migrate_escrow::*;
+pub use migrate_oft_type::*;
+pub use oft_status::*;
+pub use pause_status::*;
+pub use peer_stats::*;
+pub use queue_change::*;
 pub use quote_oft::*;
+pub use quote_receive::*;
 pub use quote_send::*;
+pub use rate_limit_status::*;
+pub use remove_fee_exemption::*;
 pub use send::*;
+pub use set_fee_distribution::*;
+pub use set_lz_receive_types_accounts::*;
 pub use set_oft_config::*;
 pub use set_pause::*;
 pub use set_peer_config::*;
 pub use withdraw_fee::*;
+pub use withdraw_native_fee::*;
+pub use withdraw_rent_vault::*;
@@ -0,0 +1,107 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+/// Read-only snapshot for monitoring. The peers of params.remote_eids are passed as remaining
+/// accounts, in the same order.
+#[derive(Accounts)]
+pub struct OFTStatus<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(address = oft_store.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(address = oft_store.token_escrow)]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl OFTStatus<'_> {
+    pub fn apply(ctx: &Context<OFTStatus>, params: &OFTStatusParams) -> Result<OFTStatusResult> {
+        require!(ctx.remaining_accounts.len() == params.remote_eids.len(), OFTError::InvalidPeer);
+        let oft_store = &ctx.accounts.oft_store;
+        let oft_store_key = oft_store.key();
+        let current_time = RateLimiter::now()?;
+        let peers = params
+            .remote_eids
+            .iter()
+            .zip(ctx.remaining_accounts)
+            .map(|(remote_eid, peer_info)| {
+                require_keys_eq!(*peer_info.owner, ID, OFTError::InvalidPeer);
+                let peer = PeerConfig::try_deserialize(&mut &peer_info.try_borrow_data()?[..])?;
+                let peer_key = Pubkey::create_program_address(
+                    &[PEER_SEED, oft_store_key.as_ref(), &remote_eid.to_be_bytes(), &[peer.bump]],
+                    &ID,
+                )
+                .map_err(|_| OFTError::InvalidPeer)?;
+                require_keys_eq!(peer_key, peer_info.key(), OFTError::InvalidPeer);
+                Ok(PeerStatus::new(*remote_eid, &peer, oft_store, current_time))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let escrow_balance_ld = ctx.accounts.token_escrow.amount;
+        Ok(OFTStatusResult {
+            paused: PauseState::new(oft_store.paused, oft_store.paused_until, now),
+            admin: oft_store.admin,
+            pauser: oft_store.pauser,
+            unpauser: oft_store.unpauser,
+            tvl_ld: oft_store.tvl_ld,
+            escrow_balance_ld,
+            withdrawable_fee_ld: escrow_balance_ld.saturating_sub(oft_store.tvl_ld),
+            mint_supply: ctx.accounts.token_mint.supply,
+            mint_authority: ctx.accounts.token_mint.mint_authority.into(),
+            peers,
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct OFTStatusParams {
+    pub remote_eids: Vec<u32>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct OFTStatusResult {
+    pub paused: PauseState,
+    pub admin: Pubkey,
+    pub pauser: Option<Pubkey>,
+    pub unpauser: Option<Pubkey>,
+    pub tvl_ld: u64,
+    pub escrow_balance_ld: u64,
+    pub withdrawable_fee_ld: u64,
+    pub mint_supply: u64,
+    pub mint_authority: Option<Pubkey>,
+    pub peers: Vec<PeerStatus>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PeerStatus {
+    pub remote_eid: u32,
+    pub peer_address: [u8; 32],
+    pub fee_bps: u16, // the effective flat fee, unless a fee_schedule is set
+    pub fee_schedule: Option<FeeSchedule>,
+    pub native_fee: Option<u64>,
+    pub outbound_available_ld: Option<u64>, // None if there is no rate limiter
+    pub inbound_available_ld: Option<u64>,
+}
+
+impl PeerStatus {
+    fn new(remote_eid: u32, peer: &PeerConfig, oft_store: &OFTStore, current_time: u64) -> Self {
+        PeerStatus {
+            remote_eid,
+            peer_address: peer.peer_address,
+            fee_bps: peer.fee_bps.unwrap_or(oft_store.default_fee_bps),
+            fee_schedule: peer.fee_schedule.clone(),
+            native_fee: peer.native_fee,
+            outbound_available_ld: peer
+                .outbound_rate_limiter
+                .as_ref()
+                .map(|limiter| limiter.projected_tokens(current_time)),
+            inbound_available_ld: peer
+                .inbound_rate_limiter
+                .as_ref()
+                .map(|limiter| limiter.projected_tokens(current_time)),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: PauseStatusParams)]
+pub struct PauseStatus<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    // Only required when querying a peer
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.unwrap_or_default().to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Option<Account<'info, PeerConfig>>,
+}
+
+impl PauseStatus<'_> {
+    pub fn apply(
+        ctx: &Context<PauseStatus>,
+        params: &PauseStatusParams,
+    ) -> Result<PauseStatusResult> {
+        let now = Clock::get()?.unix_timestamp;
+        let oft_store = &ctx.accounts.oft_store;
+        let mut result = PauseStatusResult {
+            oft: PauseState::new(oft_store.paused, oft_store.paused_until, now),
+            outbound: None,
+            inbound: None,
+        };
+        if params.remote_eid.is_some() {
+            let peer = ctx.accounts.peer.as_ref().ok_or(OFTError::InvalidPeer)?;
+            result.outbound =
+                Some(PauseState::new(peer.outbound_paused, peer.outbound_paused_until, now));
+            result.inbound =
+                Some(PauseState::new(peer.inbound_paused, peer.inbound_paused_until, now));
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PauseStatusParams {
+    pub remote_eid: Option<u32>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PauseStatusResult {
+    pub oft: PauseState,
+    pub outbound: Option<PauseState>, // only set when a peer is queried
+    pub inbound: Option<PauseState>,
+}
+
+/// The effective pause state. An expired pause is reported as not paused.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PauseState {
+    pub paused: bool,
+    pub until: Option<i64>,
+}
+
+impl PauseState {
+    pub fn new(paused: bool, paused_until: Option<i64>, now: i64) -> Self {
+        if is_pause_active(paused, paused_until, now) {
+            PauseState { paused: true, until: paused_until }
+        } else {
+            PauseState { paused: false, until: None }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: PeerStatsParams)]
+pub struct PeerStats<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl PeerStats<'_> {
+    pub fn apply(ctx: &Context<PeerStats>, _params: &PeerStatsParams) -> Result<PeerStatsResult> {
+        Ok(PeerStatsResult {
+            stats: ctx.accounts.peer.stats.clone(),
+            tvl_ld: ctx.accounts.oft_store.tvl_ld,
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PeerStatsParams {
+    pub remote_eid: u32,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PeerStatsResult {
+    pub stats: TransferStats,
+    pub tvl_ld: u64,
+}
@@ -0,0 +1,52 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: QueueChangeParams)]
+pub struct QueueChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PendingChange::space(&params.change),
+        seeds = [
+            PENDING_CHANGE_SEED,
+            oft_store.key().as_ref(),
+            &oft_store.change_nonce.to_be_bytes()
+        ],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+    pub system_program: Program<'info, System>,
+}
+
+impl QueueChange<'_> {
+    pub fn apply(ctx: &mut Context<QueueChange>, params: &QueueChangeParams) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let execute_after = now
+            .checked_add_unsigned(ctx.accounts.oft_store.timelock_delay)
+            .ok_or(OFTError::InvalidTimelockDelay)?;
+
+        ctx.accounts.pending_change.oft_store = ctx.accounts.oft_store.key();
+        ctx.accounts.pending_change.nonce = ctx.accounts.oft_store.change_nonce;
+        ctx.accounts.pending_change.payer = ctx.accounts.admin.key();
+        ctx.accounts.pending_change.execute_after = execute_after;
+        ctx.accounts.pending_change.change = params.change.clone();
+        ctx.accounts.pending_change.bump = ctx.bumps.pending_change;
+
+        ctx.accounts.oft_store.change_nonce += 1;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct QueueChangeParams {
+    pub change: ConfigChange,
+}
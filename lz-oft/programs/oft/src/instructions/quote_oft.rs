@@ -5,7 +5,7 @@ use anchor_spl::token_interface::Mint;
 #[instruction(params: QuoteOFTParams)]
 pub struct QuoteOFT<'info> {
     #[account(
-        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
         bump = oft_store.bump
     )]
     pub oft_store: Account<'info, OFTStore>,
@@ -20,21 +20,51 @@ pub struct QuoteOFT<'info> {
     pub peer: Account<'info, PeerConfig>,
     #[account(address = oft_store.token_mint)]
     pub token_mint: InterfaceAccount<'info, Mint>,
+    /// the fee exemption of params.sender, if any
+    #[account(
+        seeds = [FEE_EXEMPTION_SEED, oft_store.key().as_ref(), params.sender.as_ref()],
+        bump = fee_exemption.bump
+    )]
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
 }
 
 impl QuoteOFT<'_> {
     pub fn apply(ctx: &Context<QuoteOFT>, params: &QuoteOFTParams) -> Result<QuoteOFTResult> {
-        require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+        let now = Clock::get()?.unix_timestamp;
+        require!(!ctx.accounts.oft_store.is_paused(now), OFTError::Paused);
+        require!(!ctx.accounts.peer.is_outbound_paused(now), OFTError::Paused);
 
         let (amount_sent_ld, amount_received_ld, oft_fee_ld) = compute_fee_and_adjust_amount(
             params.amount_ld,
             &ctx.accounts.oft_store,
             &ctx.accounts.token_mint,
-            ctx.accounts.peer.fee_bps,
+            &ctx.accounts.peer,
+            ctx.accounts.fee_exemption.is_some(),
         )?;
         require!(amount_received_ld >= params.min_amount_ld, OFTError::SlippageExceeded);
 
-        let oft_limits = OFTLimits { min_amount_ld: 0, max_amount_ld: 0xffffffffffffffff };
+        // the effective max is also bounded by what the outbound rate limiters allow right now. They
+        // consume the amount received, so their capacity is converted back to an amount sent.
+        let mut oft_limits = ctx.accounts.peer.get_oft_limits(&ctx.accounts.oft_store);
+        let current_time = RateLimiter::now()?;
+        let capacity_ld = [
+            &ctx.accounts.peer.outbound_rate_limiter,
+            &ctx.accounts.oft_store.outbound_rate_limiter,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|rate_limiter| rate_limiter.projected_tokens(current_time))
+        .min();
+        if let Some(capacity_ld) = capacity_ld {
+            oft_limits.max_amount_ld = max_amount_sent_ld(
+                oft_limits.max_amount_ld,
+                capacity_ld,
+                &ctx.accounts.oft_store,
+                &ctx.accounts.token_mint,
+                &ctx.accounts.peer,
+                ctx.accounts.fee_exemption.is_some(),
+            )?;
+        }
         let mut oft_fee_details = if amount_received_ld + oft_fee_ld < amount_sent_ld {
             vec![OFTFeeDetail {
                 fee_amount_ld: amount_sent_ld - oft_fee_ld - amount_received_ld,
@@ -44,15 +74,59 @@ impl QuoteOFT<'_> {
             vec![]
         };
         // cross chain fee
-        if oft_fee_ld > 0 {
+        if ctx.accounts.fee_exemption.is_some() {
             oft_fee_details.push(OFTFeeDetail {
-                fee_amount_ld: oft_fee_ld,
+                fee_amount_ld: 0,
+                description: "Cross Chain Fee Exempt".to_string(),
+            });
+        } else if oft_fee_ld > 0 {
+            let referral_fee_ld =
+                calculate_referral_fee(oft_fee_ld, &ctx.accounts.oft_store, &params.referral)?;
+            oft_fee_details.push(OFTFeeDetail {
+                fee_amount_ld: oft_fee_ld - referral_fee_ld,
                 description: "Cross Chain Fee".to_string(),
             });
+            if referral_fee_ld > 0 {
+                oft_fee_details.push(OFTFeeDetail {
+                    fee_amount_ld: referral_fee_ld,
+                    description: "Referral Fee".to_string(),
+                });
+            }
         }
+        let oft_native_fee =
+            calculate_native_fee(&ctx.accounts.peer, ctx.accounts.fee_exemption.is_some());
         let oft_receipt = OFTReceipt { amount_sent_ld, amount_received_ld };
-        Ok(QuoteOFTResult { oft_limits, oft_fee_details, oft_receipt })
+        Ok(QuoteOFTResult { oft_limits, oft_fee_details, oft_receipt, oft_native_fee })
+    }
+}
+
+/// Returns the largest amount, up to max_amount_ld, whose amount_received_ld is within capacity_ld.
+fn max_amount_sent_ld(
+    max_amount_ld: u64,
+    capacity_ld: u64,
+    oft_store: &OFTStore,
+    token_mint: &InterfaceAccount<Mint>,
+    peer: &PeerConfig,
+    fee_exempt: bool,
+) -> Result<u64> {
+    let amount_received_ld = |amount_ld| {
+        compute_fee_and_adjust_amount(amount_ld, oft_store, token_mint, peer, fee_exempt)
+            .map(|(_, amount_received_ld, _)| amount_received_ld)
+    };
+    if amount_received_ld(max_amount_ld)? <= capacity_ld {
+        return Ok(max_amount_ld);
+    }
+    // the amount received grows with the amount sent, so the largest amount is bisected
+    let (mut low, mut high) = (0, max_amount_ld);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if amount_received_ld(mid)? <= capacity_ld {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
     }
+    Ok(low)
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -64,6 +138,8 @@ pub struct QuoteOFTParams {
     pub options: Vec<u8>,
     pub compose_msg: Option<Vec<u8>>,
     pub pay_in_lz_token: bool,
+    pub referral: Option<ReferralParams>,
+    pub sender: Pubkey, // the signer of the send to quote for
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -71,6 +147,7 @@ pub struct QuoteOFTResult {
     pub oft_limits: OFTLimits,
     pub oft_fee_details: Vec<OFTFeeDetail>,
     pub oft_receipt: OFTReceipt,
+    pub oft_native_fee: u64, // in lamports, paid by the sender on top of the messaging fee
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -85,8 +162,29 @@ pub struct OFTReceipt {
     pub amount_received_ld: u64,
 }
 
-#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
 pub struct OFTLimits {
     pub min_amount_ld: u64,
     pub max_amount_ld: u64,
 }
+
+impl Default for OFTLimits {
+    fn default() -> Self {
+        OFTLimits { min_amount_ld: 0, max_amount_ld: u64::MAX }
+    }
+}
+
+impl OFTLimits {
+    pub fn is_valid(&self) -> bool {
+        self.min_amount_ld <= self.max_amount_ld
+    }
+
+    pub fn contains(&self, amount_ld: u64) -> bool {
+        amount_ld >= self.min_amount_ld && amount_ld <= self.max_amount_ld
+    }
+
+    /// Raising the max or lowering the min allows transfers that were rejected before.
+    pub fn loosens(&self, current: &OFTLimits) -> bool {
+        self.max_amount_ld > current.max_amount_ld || self.min_amount_ld < current.min_amount_ld
+    }
+}
@@ -0,0 +1,126 @@
+use crate::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_2022::spl_token_2022::{
+        self,
+        extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+        solana_program::program_pack::Pack,
+        state::{Account as TokenAccountState, Mint as MintState},
+    },
+    token_interface::Mint,
+};
+
+/// Predicts what the recipient gets for an inbound transfer, so the sending side can show
+/// the rent fee that is deducted when the destination ATA does not exist yet. The fee is only
+/// deducted if the rent vault can pay the rent back, so it has to be passed to quote it.
+#[derive(Accounts)]
+#[instruction(params: QuoteReceiveParams)]
+pub struct QuoteReceive<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.src_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    #[account(address = oft_store.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: the ATA of params.to, or params.to itself if it is a token account
+    pub token_dest: AccountInfo<'info>,
+    #[account(
+        seeds = [RENT_VAULT_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub rent_vault: Option<SystemAccount<'info>>,
+}
+
+impl QuoteReceive<'_> {
+    pub fn apply(
+        ctx: &Context<QuoteReceive>,
+        params: &QuoteReceiveParams,
+    ) -> Result<QuoteReceiveResult> {
+        let to = Pubkey::from(params.to);
+        // native SOL mode pays lamports, there is no ATA to create
+        let unwrap_sol = ctx.accounts.oft_store.native_sol && !params.to_token_account;
+        let token_dest = if params.to_token_account {
+            to
+        } else if unwrap_sol {
+            Pubkey::find_program_address(
+                &[NATIVE_SOL_SEED, ctx.accounts.oft_store.key().as_ref()],
+                ctx.program_id,
+            )
+            .0
+        } else {
+            get_associated_token_address_with_program_id(
+                &to,
+                &ctx.accounts.oft_store.token_mint,
+                ctx.accounts.token_mint.to_account_info().owner,
+            )
+        };
+        require_keys_eq!(ctx.accounts.token_dest.key(), token_dest, OFTError::InvalidTokenDest);
+
+        let amount_ld = ctx.accounts.oft_store.remove_dust(params.amount_ld);
+        let ata_rent_fee_ld = if !params.to_token_account
+            && !unwrap_sol
+            && !params.composed
+            && ctx.accounts.token_dest.data_is_empty()
+            && Self::is_rent_reimbursable(ctx)?
+        {
+            ctx.accounts.peer.get_ata_rent_fee_ld(amount_ld)
+        } else {
+            0
+        };
+        let amount_received_ld = if ctx.accounts.oft_store.oft_type == OFTType::Native {
+            amount_ld - ata_rent_fee_ld
+        } else {
+            get_post_fee_amount_ld(&ctx.accounts.token_mint, amount_ld - ata_rent_fee_ld)?
+        };
+        Ok(QuoteReceiveResult { amount_received_ld, ata_rent_fee_ld })
+    }
+
+    // Whether the rent vault covers the rent of the ATA, which lz_receive requires to charge the fee.
+    fn is_rent_reimbursable(ctx: &Context<QuoteReceive>) -> Result<bool> {
+        let Some(rent_vault) = &ctx.accounts.rent_vault else {
+            return Ok(false);
+        };
+        let rent = Rent::get()?;
+        let ata_len = get_ata_len(&ctx.accounts.token_mint.to_account_info())?;
+        Ok(rent_vault_spare_lamports(rent_vault.lamports(), &rent) >= rent.minimum_balance(ata_len))
+    }
+}
+
+/// The size of the ATA the associated token program creates for the mint: a Token-2022 account
+/// carries the extensions the mint requires and ImmutableOwner.
+pub fn get_ata_len(token_mint: &AccountInfo) -> Result<usize> {
+    if *token_mint.owner != spl_token_2022::ID {
+        return Ok(TokenAccountState::LEN);
+    }
+    let token_mint_data = token_mint.try_borrow_data()?;
+    let token_mint_ext = StateWithExtensions::<MintState>::unpack(&token_mint_data)?;
+    let mut extensions =
+        ExtensionType::get_required_init_account_extensions(&token_mint_ext.get_extension_types()?);
+    extensions.push(ExtensionType::ImmutableOwner);
+    Ok(ExtensionType::try_calculate_account_len::<TokenAccountState>(&extensions)?)
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct QuoteReceiveParams {
+    pub src_eid: u32,
+    pub to: [u8; 32],
+    pub amount_ld: u64,
+    pub to_token_account: bool,
+    pub composed: bool, // composed messages are exempt from the rent fee
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct QuoteReceiveResult {
+    pub amount_received_ld: u64,
+    pub ata_rent_fee_ld: u64,
+}
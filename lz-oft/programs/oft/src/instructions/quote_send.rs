@@ -16,7 +16,7 @@ use anchor_spl::{
 #[instruction(params: QuoteSendParams)]
 pub struct QuoteSend<'info> {
     #[account(
-        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
         bump = oft_store.bump
     )]
     pub oft_store: Account<'info, OFTStore>,
@@ -31,17 +31,31 @@ pub struct QuoteSend<'info> {
     pub peer: Account<'info, PeerConfig>,
     #[account(address = oft_store.token_mint)]
     pub token_mint: InterfaceAccount<'info, Mint>,
+    /// the fee exemption of params.sender, if any
+    #[account(
+        seeds = [FEE_EXEMPTION_SEED, oft_store.key().as_ref(), params.sender.as_ref()],
+        bump = fee_exemption.bump
+    )]
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
 }
 
 impl QuoteSend<'_> {
     pub fn apply(ctx: &Context<QuoteSend>, params: &QuoteSendParams) -> Result<MessagingFee> {
-        require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+        let now = Clock::get()?.unix_timestamp;
+        require!(!ctx.accounts.oft_store.is_paused(now), OFTError::Paused);
+        require!(!ctx.accounts.peer.is_outbound_paused(now), OFTError::Paused);
+        require!(
+            !params.to_token_account
+                || ctx.accounts.peer.accepts_to_token_account(&params.compose_msg),
+            OFTError::InvalidTokenDest
+        );
 
         let (_, amount_received_ld, _) = compute_fee_and_adjust_amount(
             params.amount_ld,
             &ctx.accounts.oft_store,
             &ctx.accounts.token_mint,
-            ctx.accounts.peer.fee_bps,
+            &ctx.accounts.peer,
+            ctx.accounts.fee_exemption.is_some(),
         )?;
         require!(amount_received_ld >= params.min_amount_ld, OFTError::SlippageExceeded);
 
@@ -53,12 +67,16 @@ impl QuoteSend<'_> {
                 sender: ctx.accounts.oft_store.key(),
                 dst_eid: params.dst_eid,
                 receiver: ctx.accounts.peer.peer_address,
-                message: msg_codec::encode(
-                    params.to,
-                    amount_received_ld,
-                    Pubkey::default(),
-                    &params.compose_msg,
-                ),
+                message: if params.to_token_account {
+                    msg_codec::encode_to_token_account(params.to, amount_received_ld)
+                } else {
+                    msg_codec::encode(
+                        params.to,
+                        amount_received_ld,
+                        Pubkey::default(),
+                        &params.compose_msg,
+                    )
+                },
                 pay_in_lz_token: params.pay_in_lz_token,
                 options: ctx
                     .accounts
@@ -74,8 +92,16 @@ pub fn compute_fee_and_adjust_amount(
     amount_ld: u64,
     oft_store: &OFTStore,
     token_mint: &InterfaceAccount<Mint>,
-    fee_bps: Option<u16>,
+    peer: &PeerConfig,
+    fee_exempt: bool,
 ) -> Result<(u64, u64, u64)> {
+    let oft_fee = |amount_ld: u64| {
+        if fee_exempt || peer.native_fee.is_some() {
+            0
+        } else {
+            calculate_oft_fee(amount_ld, oft_store, peer)
+        }
+    };
     let (amount_sent_ld, amount_received_ld, oft_fee_ld) = if OFTType::Adapter == oft_store.oft_type
     {
         let mut amount_received_ld =
@@ -83,27 +109,53 @@ pub fn compute_fee_and_adjust_amount(
         let amount_sent_ld = get_pre_fee_amount_ld(token_mint, amount_received_ld)?;
 
         // remove the oft fee from the amount_received_ld
-        let oft_fee_ld = oft_store.remove_dust(calculate_fee(
-            amount_received_ld,
-            oft_store.default_fee_bps,
-            fee_bps,
-        ));
+        let oft_fee_ld = oft_fee(amount_received_ld);
         amount_received_ld -= oft_fee_ld;
         (amount_sent_ld, amount_received_ld, oft_fee_ld)
     } else {
         // if it is Native OFT, there is no transfer fee
         let amount_sent_ld = oft_store.remove_dust(amount_ld);
-        let oft_fee_ld = oft_store.remove_dust(calculate_fee(
-            amount_sent_ld,
-            oft_store.default_fee_bps,
-            fee_bps,
-        ));
+        let oft_fee_ld = oft_fee(amount_sent_ld);
         let amount_received_ld = amount_sent_ld - oft_fee_ld;
         (amount_sent_ld, amount_received_ld, oft_fee_ld)
     };
     Ok((amount_sent_ld, amount_received_ld, oft_fee_ld))
 }
 
+/// Returns the dust-free OFT fee of a dust-free amount. The peer's fee schedule takes precedence
+/// over its flat fee_bps, which takes precedence over the default_fee_bps of the OFTStore.
+pub fn calculate_oft_fee(amount_ld: u64, oft_store: &OFTStore, peer: &PeerConfig) -> u64 {
+    let fee = if let Some(fee_schedule) = &peer.fee_schedule {
+        fee_schedule.calculate_fee(amount_ld)
+    } else {
+        calculate_fee(amount_ld, oft_store.default_fee_bps, peer.fee_bps)
+    };
+    oft_store.remove_dust(fee)
+}
+
+/// Returns the part of the OFT fee that is paid to the referrer.
+pub fn calculate_referral_fee(
+    oft_fee_ld: u64,
+    oft_store: &OFTStore,
+    referral: &Option<ReferralParams>,
+) -> Result<u64> {
+    let Some(referral) = referral else {
+        return Ok(0);
+    };
+    require!(referral.fee_bps <= oft_store.max_referral_fee_bps, OFTError::InvalidReferral);
+    Ok(calculate_fee(oft_fee_ld, 0, Some(referral.fee_bps)))
+}
+
+/// Returns the OFT fee in lamports, charged on top of the messaging fee when the peer is in
+/// native fee mode.
+pub fn calculate_native_fee(peer: &PeerConfig, fee_exempt: bool) -> u64 {
+    if fee_exempt {
+        0
+    } else {
+        peer.native_fee.unwrap_or(0)
+    }
+}
+
 fn calculate_fee(pre_fee_amount: u64, default_fee_bps: u16, fee_bps: Option<u16>) -> u64 {
     let final_fee_bps = if let Some(bps) = fee_bps { bps as u128 } else { default_fee_bps as u128 };
     if final_fee_bps == 0 || pre_fee_amount == 0 {
@@ -190,4 +242,6 @@ pub struct QuoteSendParams {
     pub options: Vec<u8>,
     pub compose_msg: Option<Vec<u8>>,
     pub pay_in_lz_token: bool,
+    pub to_token_account: bool, // Solana peers only
+    pub sender: Pubkey,         // the signer of the send to quote for
 }
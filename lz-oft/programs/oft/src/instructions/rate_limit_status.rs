@@ -0,0 +1,96 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: RateLimitStatusParams)]
+pub struct RateLimitStatus<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    // Only read when querying a sender. Senders without a bucket yet report a full one
+    #[account(
+        seeds = [
+            SENDER_RATE_LIMIT_SEED,
+            peer.key().as_ref(),
+            params.sender.unwrap_or_default().as_ref()
+        ],
+        bump = sender_rate_limit.bump
+    )]
+    pub sender_rate_limit: Option<Account<'info, SenderRateLimit>>,
+}
+
+impl RateLimitStatus<'_> {
+    pub fn apply(
+        ctx: &Context<RateLimitStatus>,
+        params: &RateLimitStatusParams,
+    ) -> Result<RateLimitStatusResult> {
+        let current_time = RateLimiter::now()?;
+        let peer = &ctx.accounts.peer;
+        let sender = match (&peer.sender_rate_limit, params.sender) {
+            (Some(config), Some(_)) => {
+                let mut rate_limiter = match &ctx.accounts.sender_rate_limit {
+                    Some(sender_rate_limit) => sender_rate_limit.rate_limiter.clone(),
+                    None => config.new_rate_limiter(current_time),
+                };
+                config.sync(&mut rate_limiter, current_time);
+                Some(RateLimitState::new(&rate_limiter, current_time))
+            },
+            _ => None,
+        };
+        Ok(RateLimitStatusResult {
+            sender,
+            outbound: peer
+                .outbound_rate_limiter
+                .as_ref()
+                .map(|limiter| RateLimitState::new(limiter, current_time)),
+            inbound: peer
+                .inbound_rate_limiter
+                .as_ref()
+                .map(|limiter| RateLimitState::new(limiter, current_time)),
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RateLimitStatusParams {
+    pub remote_eid: u32,
+    pub sender: Option<Pubkey>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RateLimitStatusResult {
+    pub outbound: Option<RateLimitState>, // None if there is no rate limiter
+    pub inbound: Option<RateLimitState>,
+    pub sender: Option<RateLimitState>, // None if no sender is queried or the peer has no limit
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RateLimitState {
+    pub limiter_type: RateLimiterType,
+    pub capacity: u64,
+    pub refill_per_second: u64,
+    pub available_tokens: u64,
+    pub seconds_to_full: Option<u64>, // None if the limiter never refills
+}
+
+impl RateLimitState {
+    pub fn new(rate_limiter: &RateLimiter, current_time: u64) -> Self {
+        RateLimitState {
+            limiter_type: rate_limiter.limiter_type.clone(),
+            capacity: rate_limiter.capacity,
+            refill_per_second: rate_limiter.refill_per_second,
+            available_tokens: rate_limiter.projected_tokens(current_time),
+            seconds_to_full: rate_limiter.seconds_to_full(current_time),
+        }
+    }
+}
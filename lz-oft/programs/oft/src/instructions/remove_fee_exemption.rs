@@ -0,0 +1,26 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RemoveFeeExemption<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [FEE_EXEMPTION_SEED, oft_store.key().as_ref(), fee_exemption.sender.as_ref()],
+        bump = fee_exemption.bump,
+        close = admin
+    )]
+    pub fee_exemption: Account<'info, FeeExemption>,
+}
+
+impl RemoveFeeExemption<'_> {
+    pub fn apply(_ctx: &mut Context<RemoveFeeExemption>) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::*;
+use anchor_lang::system_program;
 use anchor_spl::token_interface::{
-    self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+    self, Burn, Mint, SyncNative, TokenAccount, TokenInterface, TransferChecked,
 };
 use oapp::endpoint::{instructions::SendParams as EndpointSendParams, MessagingReceipt};
 
@@ -8,6 +9,7 @@ use oapp::endpoint::{instructions::SendParams as EndpointSendParams, MessagingRe
 #[derive(Accounts)]
 #[instruction(params: SendParams)]
 pub struct Send<'info> {
+    #[account(mut)]
     pub signer: Signer<'info>,
     #[account(
         mut,
@@ -21,17 +23,18 @@ pub struct Send<'info> {
     pub peer: Account<'info, PeerConfig>,
     #[account(
         mut,
-        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
         bump = oft_store.bump
     )]
     pub oft_store: Account<'info, OFTStore>,
+    /// required unless the adapter is in native SOL mode, which takes the lamports of the signer
     #[account(
         mut,
         token::authority = signer,
         token::mint = token_mint,
         token::token_program = token_program
     )]
-    pub token_source: InterfaceAccount<'info, TokenAccount>,
+    pub token_source: Option<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         address = oft_store.token_escrow,
@@ -47,47 +50,140 @@ pub struct Send<'info> {
     )]
     pub token_mint: InterfaceAccount<'info, Mint>,
     pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [FEE_EXEMPTION_SEED, oft_store.key().as_ref(), signer.key().as_ref()],
+        bump = fee_exemption.bump
+    )]
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+    /// required if the peer charges a native fee, paid by the signer.
+    /// the system program is also required in native SOL mode
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<SystemAccount<'info>>,
+    pub system_program: Option<Program<'info, System>>,
+    /// required if params.referral is set
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub referrer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// required if the peer has a sender rate limit
+    #[account(
+        mut,
+        seeds = [SENDER_RATE_LIMIT_SEED, peer.key().as_ref(), signer.key().as_ref()],
+        bump = sender_rate_limit.bump
+    )]
+    pub sender_rate_limit: Option<Account<'info, SenderRateLimit>>,
 }
 
-impl Send<'_> {
+impl<'info> Send<'info> {
     pub fn apply(
-        ctx: &mut Context<Send>,
+        ctx: &mut Context<'_, '_, '_, 'info, Send<'info>>,
         params: &SendParams,
     ) -> Result<(MessagingReceipt, OFTReceipt)> {
-        require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+        require!(
+            !params.to_token_account
+                || ctx.accounts.peer.accepts_to_token_account(&params.compose_msg),
+            OFTError::InvalidTokenDest
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(!ctx.accounts.oft_store.is_paused(now), OFTError::Paused);
+        require!(!ctx.accounts.peer.is_outbound_paused(now), OFTError::Paused);
 
         let (amount_sent_ld, amount_received_ld, oft_fee_ld) = compute_fee_and_adjust_amount(
             params.amount_ld,
             &ctx.accounts.oft_store,
             &ctx.accounts.token_mint,
-            ctx.accounts.peer.fee_bps,
+            &ctx.accounts.peer,
+            ctx.accounts.fee_exemption.is_some(),
         )?;
         require!(amount_received_ld >= params.min_amount_ld, OFTError::SlippageExceeded);
+        require!(
+            ctx.accounts
+                .peer
+                .get_oft_limits(&ctx.accounts.oft_store)
+                .contains(amount_sent_ld),
+            OFTError::InvalidAmount
+        );
 
+        // the transfer hook accounts, if any, lead the accounts for the endpoint. The hook may
+        // resolve different accounts per transfer, so the transfer from the token source and the
+        // referral transfer, if params.referral is set, each have their own
+        let token_mint = ctx.accounts.token_mint.to_account_info();
+        let (hook_accounts, remaining_accounts) =
+            transfer_hook::split_accounts(&token_mint, ctx.remaining_accounts)?;
+        let (referral_hook_accounts, endpoint_accounts) = if params.referral.is_some() {
+            transfer_hook::split_accounts(&token_mint, remaining_accounts)?
+        } else {
+            (&[][..], remaining_accounts)
+        };
+
+        let current_time = RateLimiter::now()?;
         if let Some(rate_limiter) = ctx.accounts.peer.outbound_rate_limiter.as_mut() {
-            rate_limiter.try_consume(amount_received_ld)?;
+            rate_limiter.try_consume(amount_received_ld, current_time)?;
         }
         if let Some(rate_limiter) = ctx.accounts.peer.inbound_rate_limiter.as_mut() {
-            rate_limiter.refill(amount_received_ld)?;
+            rate_limiter.refill(amount_received_ld, current_time);
+        }
+        if let Some(rate_limiter) = ctx.accounts.oft_store.outbound_rate_limiter.as_mut() {
+            rate_limiter.try_consume(amount_received_ld, current_time)?;
+        }
+        if let Some(rate_limiter) = ctx.accounts.oft_store.inbound_rate_limiter.as_mut() {
+            rate_limiter.refill(amount_received_ld, current_time);
+        }
+        if let Some(config) = &ctx.accounts.peer.sender_rate_limit {
+            let sender_rate_limit =
+                ctx.accounts.sender_rate_limit.as_mut().ok_or(OFTError::InvalidRateLimiter)?;
+            config.sync(&mut sender_rate_limit.rate_limiter, current_time);
+            sender_rate_limit.rate_limiter.try_consume(amount_received_ld, current_time)?;
         }
 
         if ctx.accounts.oft_store.oft_type == OFTType::Adapter {
             // transfer all tokens to escrow with fee
             ctx.accounts.oft_store.tvl_ld += amount_received_ld;
-            token_interface::transfer_checked(
-                CpiContext::new(
+            if ctx.accounts.oft_store.native_sol {
+                // wrap the lamports of the signer into the escrow
+                let system_program =
+                    ctx.accounts.system_program.as_ref().ok_or(OFTError::InvalidNativeSol)?;
+                system_program::transfer(
+                    CpiContext::new(
+                        system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: ctx.accounts.signer.to_account_info(),
+                            to: ctx.accounts.token_escrow.to_account_info(),
+                        },
+                    ),
+                    amount_sent_ld,
+                )?;
+                token_interface::sync_native(CpiContext::new(
                     ctx.accounts.token_program.to_account_info(),
-                    TransferChecked {
-                        from: ctx.accounts.token_source.to_account_info(),
-                        mint: ctx.accounts.token_mint.to_account_info(),
-                        to: ctx.accounts.token_escrow.to_account_info(),
-                        authority: ctx.accounts.signer.to_account_info(),
-                    },
-                ),
-                amount_sent_ld,
-                ctx.accounts.token_mint.decimals,
-            )?;
+                    SyncNative { account: ctx.accounts.token_escrow.to_account_info() },
+                ))?;
+            } else {
+                let token_source =
+                    ctx.accounts.token_source.as_ref().ok_or(OFTError::InvalidTokenSource)?;
+                transfer_hook::transfer_checked(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            from: token_source.to_account_info(),
+                            mint: ctx.accounts.token_mint.to_account_info(),
+                            to: ctx.accounts.token_escrow.to_account_info(),
+                            authority: ctx.accounts.signer.to_account_info(),
+                        },
+                    ),
+                    amount_sent_ld,
+                    ctx.accounts.token_mint.decimals,
+                    hook_accounts,
+                )?;
+            }
         } else {
+            let token_source =
+                ctx.accounts.token_source.as_ref().ok_or(OFTError::InvalidTokenSource)?;
             // Native type
             // burn
             token_interface::burn(
@@ -95,7 +191,7 @@ impl Send<'_> {
                     ctx.accounts.token_program.to_account_info(),
                     Burn {
                         mint: ctx.accounts.token_mint.to_account_info(),
-                        from: ctx.accounts.token_source.to_account_info(),
+                        from: token_source.to_account_info(),
                         authority: ctx.accounts.signer.to_account_info(),
                     },
                 ),
@@ -104,11 +200,11 @@ impl Send<'_> {
 
             // transfer fee to escrow
             if oft_fee_ld > 0 {
-                token_interface::transfer_checked(
+                transfer_hook::transfer_checked(
                     CpiContext::new(
                         ctx.accounts.token_program.to_account_info(),
                         TransferChecked {
-                            from: ctx.accounts.token_source.to_account_info(),
+                            from: token_source.to_account_info(),
                             mint: ctx.accounts.token_mint.to_account_info(),
                             to: ctx.accounts.token_escrow.to_account_info(),
                             authority: ctx.accounts.signer.to_account_info(),
@@ -116,30 +212,106 @@ impl Send<'_> {
                     ),
                     oft_fee_ld,
                     ctx.accounts.token_mint.decimals,
+                    hook_accounts,
                 )?;
             }
         }
 
+        // the referrer is paid its share of the OFT fee out of the escrow
+        let referral_fee_ld =
+            calculate_referral_fee(oft_fee_ld, &ctx.accounts.oft_store, &params.referral)?;
+        if referral_fee_ld > 0 {
+            let referrer_token_account = ctx
+                .accounts
+                .referrer_token_account
+                .as_ref()
+                .filter(|account| {
+                    params.referral.as_ref().is_some_and(|r| r.referrer == account.key())
+                })
+                .ok_or(OFTError::InvalidReferral)?;
+            transfer_hook::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.token_escrow.to_account_info(),
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        to: referrer_token_account.to_account_info(),
+                        authority: ctx.accounts.oft_store.to_account_info(),
+                    },
+                )
+                .with_signer(&[&[
+                    OFT_SEED,
+                    ctx.accounts.oft_store.escrow_seed.as_ref(),
+                    &[ctx.accounts.oft_store.bump],
+                ]]),
+                referral_fee_ld,
+                ctx.accounts.token_mint.decimals,
+                referral_hook_accounts,
+            )?;
+        }
+        ctx.accounts.oft_store.total_fee_ld += oft_fee_ld - referral_fee_ld;
+        ctx.accounts
+            .peer
+            .stats
+            .record_sent(amount_received_ld, oft_fee_ld - referral_fee_ld);
+
+        let oft_native_fee =
+            calculate_native_fee(&ctx.accounts.peer, ctx.accounts.fee_exemption.is_some());
+        if oft_native_fee > 0 {
+            let (Some(fee_vault), Some(system_program)) =
+                (&ctx.accounts.fee_vault, &ctx.accounts.system_program)
+            else {
+                return err!(OFTError::InvalidFeeVault);
+            };
+            // a deposit that leaves the vault below the rent-exempt minimum would fail the runtime
+            // rent check, the vault of an OFT created before init_oft funded it needs init_fee_vault
+            require!(
+                fee_vault_shortfall(
+                    fee_vault.lamports().saturating_add(oft_native_fee),
+                    &Rent::get()?
+                ) == 0,
+                OFTError::InvalidFeeVault
+            );
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.signer.to_account_info(),
+                        to: fee_vault.to_account_info(),
+                    },
+                ),
+                oft_native_fee,
+            )?;
+        }
+
         // send message to endpoint
         require!(
-            ctx.accounts.oft_store.key() == ctx.remaining_accounts[1].key(),
+            ctx.accounts.oft_store.key() == endpoint_accounts[1].key(),
             OFTError::InvalidSender
         );
         let amount_sd = ctx.accounts.oft_store.ld2sd(amount_received_ld);
         let msg_receipt = oapp::endpoint_cpi::send(
             ctx.accounts.oft_store.endpoint_program,
             ctx.accounts.oft_store.key(),
-            ctx.remaining_accounts,
-            &[OFT_SEED, ctx.accounts.token_escrow.key().as_ref(), &[ctx.accounts.oft_store.bump]],
+            endpoint_accounts,
+            &[
+                OFT_SEED,
+                ctx.accounts.oft_store.escrow_seed.as_ref(),
+                &[ctx.accounts.oft_store.bump],
+            ],
             EndpointSendParams {
                 dst_eid: params.dst_eid,
                 receiver: ctx.accounts.peer.peer_address,
-                message: msg_codec::encode(
-                    params.to,
-                    amount_sd,
-                    ctx.accounts.signer.key(),
-                    &params.compose_msg,
-                ),
+                message: if params.to_token_account {
+                    msg_codec::encode_to_token_account(params.to, amount_sd)
+                } else {
+                    msg_codec::encode(
+                        params.to,
+                        amount_sd,
+                        ctx.accounts.signer.key(),
+                        &params.compose_msg,
+                    )
+                },
                 options: ctx
                     .accounts
                     .peer
@@ -153,9 +325,17 @@ impl Send<'_> {
         emit_cpi!(OFTSent {
             guid: msg_receipt.guid,
             dst_eid: params.dst_eid,
-            from: ctx.accounts.token_source.key(),
+            // in native SOL mode the lamports come from the signer
+            from: ctx
+                .accounts
+                .token_source
+                .as_ref()
+                .map_or(ctx.accounts.signer.key(), |token_source| token_source.key()),
             amount_sent_ld,
-            amount_received_ld
+            amount_received_ld,
+            oft_fee_ld,
+            referral_fee_ld,
+            oft_native_fee
         });
 
         Ok((msg_receipt, OFTReceipt { amount_sent_ld, amount_received_ld }))
@@ -172,4 +352,12 @@ pub struct SendParams {
     pub compose_msg: Option<Vec<u8>>,
     pub native_fee: u64,
     pub lz_token_fee: u64,
+    pub referral: Option<ReferralParams>,
+    pub to_token_account: bool, // `to` is a token account instead of a wallet. Solana peers only
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ReferralParams {
+    pub referrer: Pubkey, // token account of the referrer
+    pub fee_bps: u16,     // share of the OFT fee
 }
@@ -0,0 +1,54 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetFeeDistribution<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FeeDistribution::INIT_SPACE,
+        seeds = [FEE_DISTRIBUTION_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub fee_distribution: Account<'info, FeeDistribution>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetFeeDistribution<'_> {
+    pub fn apply(
+        ctx: &mut Context<SetFeeDistribution>,
+        params: &SetFeeDistributionParams,
+    ) -> Result<()> {
+        // redirecting the fees always has to be queued when the timelock is enabled
+        require!(!ctx.accounts.oft_store.is_timelocked(), OFTError::TimelockRequired);
+        Self::update_config(&mut ctx.accounts.fee_distribution, &ctx.accounts.oft_store, params)?;
+        ctx.accounts.fee_distribution.bump = ctx.bumps.fee_distribution;
+        Ok(())
+    }
+
+    pub(crate) fn update_config(
+        fee_distribution: &mut FeeDistribution,
+        oft_store: &Account<OFTStore>,
+        params: &SetFeeDistributionParams,
+    ) -> Result<()> {
+        require!(
+            FeeDistribution::is_valid(&params.recipients, oft_store.token_escrow),
+            OFTError::InvalidFeeDistribution
+        );
+        fee_distribution.oft_store = oft_store.key();
+        fee_distribution.recipients = params.recipients.clone();
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetFeeDistributionParams {
+    pub recipients: Vec<FeeRecipient>,
+}
@@ -0,0 +1,60 @@
+use crate::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::Mint;
+
+/// Anyone can refresh the LzReceiveTypesAccounts from the mint, e.g. after its transfer hook
+/// changed. An account created before extra_account_metas was added is reallocated at the payer's
+/// expense, as the executor only passes the accounts it lists to lz_receive_types.
+#[derive(Accounts)]
+pub struct SetLzReceiveTypesAccounts<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    /// CHECK: may be too small for the current layout, so it is rewritten as a whole
+    #[account(
+        mut,
+        seeds = [LZ_RECEIVE_TYPES_SEED, oft_store.key().as_ref()],
+        bump,
+        owner = ID
+    )]
+    pub lz_receive_types_accounts: AccountInfo<'info>,
+    #[account(address = oft_store.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetLzReceiveTypesAccounts<'_> {
+    pub fn apply(ctx: &mut Context<SetLzReceiveTypesAccounts>) -> Result<()> {
+        let account = &ctx.accounts.lz_receive_types_accounts;
+        let space = 8 + LzReceiveTypesAccounts::INIT_SPACE;
+        if account.data_len() < space {
+            let lamports = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+            if lamports > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: ctx.accounts.payer.to_account_info(),
+                            to: account.to_account_info(),
+                        },
+                    ),
+                    lamports,
+                )?;
+            }
+            account.realloc(space, false)?;
+        }
+
+        let lz_receive_types_accounts = LzReceiveTypesAccounts {
+            oft_store: ctx.accounts.oft_store.key(),
+            token_mint: ctx.accounts.token_mint.key(),
+            extra_account_metas: transfer_hook::get_extra_account_metas(
+                &ctx.accounts.token_mint.to_account_info(),
+            )?,
+        };
+        lz_receive_types_accounts.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])
+    }
+}
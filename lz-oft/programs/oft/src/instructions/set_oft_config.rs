@@ -6,7 +6,7 @@ pub struct SetOFTConfig<'info> {
     pub admin: Signer<'info>,
     #[account(
         mut,
-        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
         bump = oft_store.bump,
         has_one = admin @OFTError::Unauthorized
     )]
@@ -15,34 +15,83 @@ pub struct SetOFTConfig<'info> {
 
 impl SetOFTConfig<'_> {
     pub fn apply(ctx: &mut Context<SetOFTConfig>, params: &SetOFTConfigParams) -> Result<()> {
+        require!(
+            !ctx.accounts.oft_store.is_timelocked()
+                || !params.requires_timelock(&ctx.accounts.oft_store),
+            OFTError::TimelockRequired
+        );
+        Self::update_config(&mut ctx.accounts.oft_store, ctx.remaining_accounts, params)
+    }
+
+    pub fn update_config(
+        oft_store: &mut Account<OFTStore>,
+        remaining_accounts: &[AccountInfo],
+        params: &SetOFTConfigParams,
+    ) -> Result<()> {
         match params.clone() {
             SetOFTConfigParams::Admin(admin) => {
-                ctx.accounts.oft_store.admin = admin;
+                oft_store.admin = admin;
             },
             SetOFTConfigParams::Delegate(delegate) => {
-                let oft_store_seed = ctx.accounts.oft_store.token_escrow.key();
-                let seeds: &[&[u8]] =
-                    &[OFT_SEED, &oft_store_seed.to_bytes(), &[ctx.accounts.oft_store.bump]];
+                let oft_store_seed = oft_store.escrow_seed;
+                let seeds: &[&[u8]] = &[OFT_SEED, &oft_store_seed.to_bytes(), &[oft_store.bump]];
                 let _ = oapp::endpoint_cpi::set_delegate(
-                    ctx.accounts.oft_store.endpoint_program,
-                    ctx.accounts.oft_store.key(),
-                    &ctx.remaining_accounts,
+                    oft_store.endpoint_program,
+                    oft_store.key(),
+                    remaining_accounts,
                     seeds,
                     SetDelegateParams { delegate },
                 )?;
             },
             SetOFTConfigParams::DefaultFee(fee_bps) => {
                 require!(fee_bps < MAX_FEE_BASIS_POINTS, OFTError::InvalidFee);
-                ctx.accounts.oft_store.default_fee_bps = fee_bps;
+                oft_store.default_fee_bps = fee_bps;
+            },
+            SetOFTConfigParams::MaxReferralFee(fee_bps) => {
+                require!(fee_bps <= MAX_FEE_BASIS_POINTS, OFTError::InvalidFee);
+                oft_store.max_referral_fee_bps = fee_bps;
+            },
+            SetOFTConfigParams::DefaultOFTLimits(oft_limits) => {
+                require!(oft_limits.is_valid(), OFTError::InvalidOFTLimits);
+                oft_store.default_oft_limits = oft_limits;
             },
             SetOFTConfigParams::Paused(paused) => {
-                ctx.accounts.oft_store.paused = paused;
+                oft_store.paused = paused;
+                oft_store.paused_until = None;
             },
             SetOFTConfigParams::Pauser(pauser) => {
-                ctx.accounts.oft_store.pauser = pauser;
+                oft_store.pauser = pauser;
             },
             SetOFTConfigParams::Unpauser(unpauser) => {
-                ctx.accounts.oft_store.unpauser = unpauser;
+                oft_store.unpauser = unpauser;
+            },
+            SetOFTConfigParams::Guardian(guardian) => {
+                oft_store.guardian = guardian;
+            },
+            SetOFTConfigParams::TimelockDelay(delay) => {
+                require!(delay <= MAX_TIMELOCK_DELAY, OFTError::InvalidTimelockDelay);
+                oft_store.timelock_delay = delay;
+            },
+            SetOFTConfigParams::OutboundRateLimit(rate_limit_params) => {
+                SetPeerConfig::update_rate_limiter(
+                    &mut oft_store.outbound_rate_limiter,
+                    &rate_limit_params,
+                )?;
+            },
+            SetOFTConfigParams::InboundRateLimit(rate_limit_params) => {
+                SetPeerConfig::update_rate_limiter(
+                    &mut oft_store.inbound_rate_limiter,
+                    &rate_limit_params,
+                )?;
+            },
+            SetOFTConfigParams::NativeSol(native_sol) => {
+                require!(
+                    !native_sol
+                        || (oft_store.oft_type == OFTType::Adapter
+                            && OFTStore::is_native_mint(&oft_store.token_mint)),
+                    OFTError::InvalidNativeSol
+                );
+                oft_store.native_sol = native_sol;
             },
         }
         Ok(())
@@ -51,10 +100,48 @@ impl SetOFTConfig<'_> {
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub enum SetOFTConfigParams {
+    // the variants of the baseline program keep their index, which the SDK encodes
     Admin(Pubkey),
     Delegate(Pubkey), // OApp delegate for the endpoint
     DefaultFee(u16),
     Paused(bool),
     Pauser(Option<Pubkey>),
     Unpauser(Option<Pubkey>),
+    MaxReferralFee(u16), // in bps of the OFT fee
+    DefaultOFTLimits(OFTLimits),
+    Guardian(Option<Pubkey>),
+    TimelockDelay(u64), // at most MAX_TIMELOCK_DELAY
+    OutboundRateLimit(Option<RateLimitParams>),
+    InboundRateLimit(Option<RateLimitParams>),
+    NativeSol(bool), // only for an adapter of the native mint
+}
+
+impl SetOFTConfigParams {
+    /// Whether the change increases risk and has to be queued when the timelock is enabled.
+    /// Pausing, lowering the default fee and lengthening the delay take effect immediately.
+    pub fn requires_timelock(&self, oft_store: &OFTStore) -> bool {
+        match self {
+            SetOFTConfigParams::Admin(_)
+            | SetOFTConfigParams::Delegate(_)
+            | SetOFTConfigParams::Pauser(_)
+            | SetOFTConfigParams::Unpauser(_)
+            | SetOFTConfigParams::Guardian(_)
+            | SetOFTConfigParams::NativeSol(_) => true,
+            SetOFTConfigParams::DefaultFee(fee_bps) => *fee_bps > oft_store.default_fee_bps,
+            SetOFTConfigParams::MaxReferralFee(fee_bps) => {
+                *fee_bps > oft_store.max_referral_fee_bps
+            },
+            SetOFTConfigParams::DefaultOFTLimits(oft_limits) => {
+                oft_limits.loosens(&oft_store.default_oft_limits)
+            },
+            SetOFTConfigParams::Paused(paused) => !paused,
+            SetOFTConfigParams::TimelockDelay(delay) => *delay < oft_store.timelock_delay,
+            SetOFTConfigParams::OutboundRateLimit(params) => {
+                RateLimitParams::loosens(params, &oft_store.outbound_rate_limiter)
+            },
+            SetOFTConfigParams::InboundRateLimit(params) => {
+                RateLimitParams::loosens(params, &oft_store.inbound_rate_limiter)
+            },
+        }
+    }
 }
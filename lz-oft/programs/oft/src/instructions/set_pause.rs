@@ -7,16 +7,49 @@ pub struct SetPause<'info> {
     pub signer: Signer<'info>,
     #[account(
         mut,
-        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
         bump = oft_store.bump,
         constraint = is_valid_signer(signer.key(), &oft_store, params.paused) @OFTError::Unauthorized
     )]
     pub oft_store: Account<'info, OFTStore>,
+    // Only required when pausing a single peer
+    #[account(
+        mut,
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid().unwrap_or_default().to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Option<Account<'info, PeerConfig>>,
 }
 
 impl SetPause<'_> {
     pub fn apply(ctx: &mut Context<SetPause>, params: &SetPauseParams) -> Result<()> {
-        ctx.accounts.oft_store.paused = params.paused;
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(peer_params) = &params.peer {
+            let peer: &mut PeerConfig = ctx.accounts.peer.as_mut().ok_or(OFTError::InvalidPeer)?;
+            let mut approved_guids = peer_params.approved_guids.clone();
+            if peer_params.outbound {
+                set_pause(&mut peer.outbound_paused, &mut peer.outbound_paused_until, params, now)?;
+            }
+            if peer_params.inbound {
+                set_pause(&mut peer.inbound_paused, &mut peer.inbound_paused_until, params, now)?;
+                // resuming the inbound direction also rearms a tripped circuit breaker
+                if !params.paused {
+                    if let Some(circuit_breaker) = peer.inbound_circuit_breaker.as_mut() {
+                        let approved_guids = std::mem::take(&mut approved_guids);
+                        circuit_breaker.resume(approved_guids, RateLimiter::now()?)?;
+                    }
+                }
+            }
+            // guids can only be approved when resuming a circuit breaker
+            require!(approved_guids.is_empty(), OFTError::InvalidApprovedGuids);
+        } else {
+            let oft_store: &mut OFTStore = &mut ctx.accounts.oft_store;
+            set_pause(&mut oft_store.paused, &mut oft_store.paused_until, params, now)?;
+        }
         Ok(())
     }
 }
@@ -24,6 +57,23 @@ impl SetPause<'_> {
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct SetPauseParams {
     pub paused: bool,
+    pub peer: Option<PeerPauseParams>, // if None, the whole OFT is paused or unpaused
+    pub until: Option<i64>,            // only for pausing. if None, the pause lasts until unpaused
+}
+
+impl SetPauseParams {
+    pub fn remote_eid(&self) -> Option<u32> {
+        self.peer.as_ref().map(|peer| peer.remote_eid)
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PeerPauseParams {
+    pub remote_eid: u32,
+    pub outbound: bool,
+    pub inbound: bool,
+    // when resuming the inbound circuit breaker, the parked messages that skip its volume check
+    pub approved_guids: Vec<[u8; 32]>,
 }
 
 fn is_valid_signer(signer: Pubkey, oft_store: &OFTStore, paused: bool) -> bool {
@@ -33,3 +83,30 @@ fn is_valid_signer(signer: Pubkey, oft_store: &OFTStore, paused: bool) -> bool {
         oft_store.unpauser == Some(signer)
     }
 }
+
+// A pause with an expiry cannot shorten an active pause, so the pauser cannot use it to lift an
+// indefinite pause or bring forward the end of a longer one.
+fn set_pause(
+    paused: &mut bool,
+    paused_until: &mut Option<i64>,
+    params: &SetPauseParams,
+    now: i64,
+) -> Result<()> {
+    if params.paused {
+        if let Some(until) = params.until {
+            require!(until > now, OFTError::InvalidPauseExpiry);
+            if is_pause_active(*paused, *paused_until, now) {
+                require!(
+                    paused_until.is_some_and(|current| until >= current),
+                    OFTError::InvalidPauseExpiry
+                );
+            }
+        }
+        *paused = true;
+        *paused_until = params.until;
+    } else {
+        *paused = false;
+        *paused_until = None;
+    }
+    Ok(())
+}
@@ -14,7 +14,7 @@ pub struct SetPeerConfig<'info> {
     )]
     pub peer: Account<'info, PeerConfig>,
     #[account(
-        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
         bump = oft_store.bump,
         has_one = admin @OFTError::Unauthorized
     )]
@@ -24,50 +24,96 @@ pub struct SetPeerConfig<'info> {
 
 impl SetPeerConfig<'_> {
     pub fn apply(ctx: &mut Context<SetPeerConfig>, params: &SetPeerConfigParams) -> Result<()> {
-        match params.config.clone() {
+        require!(
+            !ctx.accounts.oft_store.is_timelocked()
+                || !params.config.requires_timelock(&ctx.accounts.peer, &ctx.accounts.oft_store),
+            OFTError::TimelockRequired
+        );
+        Self::update_config(&mut ctx.accounts.peer, &params.config)?;
+        ctx.accounts.peer.bump = ctx.bumps.peer;
+        // init_if_needed only accepts a peer of the current size, i.e. new or migrated
+        ctx.accounts.peer.version = ACCOUNT_VERSION;
+        Ok(())
+    }
+
+    pub(crate) fn update_config(peer: &mut PeerConfig, config: &PeerConfigParam) -> Result<()> {
+        match config.clone() {
             PeerConfigParam::PeerAddress(peer_address) => {
-                ctx.accounts.peer.peer_address = peer_address;
+                peer.peer_address = peer_address;
             },
             PeerConfigParam::FeeBps(fee_bps) => {
                 if let Some(fee_bps) = fee_bps {
                     require!(fee_bps < MAX_FEE_BASIS_POINTS, OFTError::InvalidFee);
                 }
-                ctx.accounts.peer.fee_bps = fee_bps;
+                peer.fee_bps = fee_bps;
+            },
+            PeerConfigParam::FeeSchedule(fee_schedule) => {
+                if let Some(fee_schedule) = &fee_schedule {
+                    require!(fee_schedule.is_valid(), OFTError::InvalidFee);
+                }
+                peer.fee_schedule = fee_schedule;
+            },
+            PeerConfigParam::NativeFee(native_fee) => {
+                peer.native_fee = native_fee;
+            },
+            PeerConfigParam::AtaRentFee(ata_rent_fee_ld) => {
+                peer.ata_rent_fee_ld = ata_rent_fee_ld;
+            },
+            PeerConfigParam::TokenAccountDest(token_account_dest) => {
+                peer.token_account_dest = token_account_dest;
+            },
+            PeerConfigParam::OFTLimits(oft_limits) => {
+                if let Some(oft_limits) = &oft_limits {
+                    require!(oft_limits.is_valid(), OFTError::InvalidOFTLimits);
+                }
+                peer.oft_limits = oft_limits;
             },
             PeerConfigParam::EnforcedOptions { send, send_and_call } => {
                 oapp::options::assert_type_3(&send)?;
-                ctx.accounts.peer.enforced_options.send = send;
+                peer.enforced_options.send = send;
                 oapp::options::assert_type_3(&send_and_call)?;
-                ctx.accounts.peer.enforced_options.send_and_call = send_and_call;
+                peer.enforced_options.send_and_call = send_and_call;
             },
             PeerConfigParam::OutboundRateLimit(rate_limit_params) => {
-                Self::update_rate_limiter(
-                    &mut ctx.accounts.peer.outbound_rate_limiter,
-                    &rate_limit_params,
-                )?;
+                Self::update_rate_limiter(&mut peer.outbound_rate_limiter, &rate_limit_params)?;
             },
             PeerConfigParam::InboundRateLimit(rate_limit_params) => {
-                Self::update_rate_limiter(
-                    &mut ctx.accounts.peer.inbound_rate_limiter,
-                    &rate_limit_params,
-                )?;
+                Self::update_rate_limiter(&mut peer.inbound_rate_limiter, &rate_limit_params)?;
+            },
+            PeerConfigParam::SenderRateLimit(sender_rate_limit) => {
+                peer.sender_rate_limit = sender_rate_limit;
+            },
+            PeerConfigParam::InboundCircuitBreaker(circuit_breaker_params) => {
+                if let Some(params) = circuit_breaker_params {
+                    let mut circuit_breaker =
+                        peer.inbound_circuit_breaker.clone().unwrap_or_default();
+                    circuit_breaker.threshold = params.threshold;
+                    circuit_breaker.window = params.window;
+                    circuit_breaker.reset(RateLimiter::now()?);
+                    peer.inbound_circuit_breaker = Some(circuit_breaker);
+                } else {
+                    peer.inbound_circuit_breaker = None;
+                }
             },
         }
-        ctx.accounts.peer.bump = ctx.bumps.peer;
         Ok(())
     }
 
-    fn update_rate_limiter(
+    pub(crate) fn update_rate_limiter(
         rate_limiter: &mut Option<RateLimiter>,
         params: &Option<RateLimitParams>,
     ) -> Result<()> {
         if let Some(param) = params {
+            let current_time = RateLimiter::now()?;
             let mut limiter = rate_limiter.clone().unwrap_or_default();
+            if let Some(limiter_type) = &param.limiter_type {
+                limiter.set_limiter_type(limiter_type.clone(), current_time)?;
+            }
             if let Some(capacity) = param.capacity {
-                limiter.set_capacity(capacity)?;
+                limiter.set_capacity(capacity, current_time);
             }
             if let Some(refill_rate) = param.refill_per_second {
-                limiter.set_rate(refill_rate)?;
+                limiter.set_rate(refill_rate, current_time);
             }
             *rate_limiter = Some(limiter);
         } else {
@@ -85,15 +131,136 @@ pub struct SetPeerConfigParams {
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub enum PeerConfigParam {
+    // the variants of the baseline program keep their index, which the SDK encodes
     PeerAddress([u8; 32]),
     FeeBps(Option<u16>),
     EnforcedOptions { send: Vec<u8>, send_and_call: Vec<u8> },
     OutboundRateLimit(Option<RateLimitParams>),
     InboundRateLimit(Option<RateLimitParams>),
+    FeeSchedule(Option<FeeSchedule>),
+    NativeFee(Option<u64>),
+    AtaRentFee(Option<u64>), // only for transfer hooks that do not depend on the amount
+    TokenAccountDest(bool),
+    OFTLimits(Option<OFTLimits>),
+    SenderRateLimit(Option<SenderRateLimitConfig>),
+    InboundCircuitBreaker(Option<CircuitBreakerParams>),
+}
+
+impl PeerConfigParam {
+    /// Whether the change increases risk and has to be queued when the timelock is enabled.
+    /// Lowering the fee, tightening a rate limiter and updating enforced options are immediate.
+    pub fn requires_timelock(&self, peer: &PeerConfig, oft_store: &OFTStore) -> bool {
+        match self {
+            PeerConfigParam::PeerAddress(_) => true,
+            PeerConfigParam::FeeBps(fee_bps) => {
+                fee_bps.unwrap_or(oft_store.default_fee_bps)
+                    > peer.fee_bps.unwrap_or(oft_store.default_fee_bps)
+            },
+            // schedules are not comparable, so any change to them is treated as a fee increase
+            PeerConfigParam::FeeSchedule(_) => true,
+            // switching between token and native fees in either direction adds a fee
+            PeerConfigParam::NativeFee(native_fee) => match (native_fee, peer.native_fee) {
+                (Some(native_fee), Some(current)) => *native_fee > current,
+                (None, None) => false,
+                _ => true,
+            },
+            PeerConfigParam::AtaRentFee(ata_rent_fee_ld) => {
+                ata_rent_fee_ld.unwrap_or(0) > peer.ata_rent_fee_ld.unwrap_or(0)
+            },
+            // a peer that does not decode the flag would misread the flagged messages
+            PeerConfigParam::TokenAccountDest(token_account_dest) => {
+                *token_account_dest && !peer.token_account_dest
+            },
+            PeerConfigParam::OFTLimits(oft_limits) => {
+                let default_oft_limits = &oft_store.default_oft_limits;
+                oft_limits
+                    .as_ref()
+                    .unwrap_or(default_oft_limits)
+                    .loosens(peer.oft_limits.as_ref().unwrap_or(default_oft_limits))
+            },
+            PeerConfigParam::EnforcedOptions { .. } => false,
+            PeerConfigParam::OutboundRateLimit(params) => {
+                RateLimitParams::loosens(params, &peer.outbound_rate_limiter)
+            },
+            PeerConfigParam::InboundRateLimit(params) => {
+                RateLimitParams::loosens(params, &peer.inbound_rate_limiter)
+            },
+            PeerConfigParam::SenderRateLimit(config) => {
+                SenderRateLimitConfig::loosens(config, &peer.sender_rate_limit)
+            },
+            PeerConfigParam::InboundCircuitBreaker(params) => {
+                match (params, &peer.inbound_circuit_breaker) {
+                    (None, current) => current.is_some(),
+                    (Some(_), None) => false,
+                    (Some(params), Some(current)) => {
+                        params.threshold > current.threshold || params.window < current.window
+                    },
+                }
+            },
+        }
+    }
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct RateLimitParams {
     pub refill_per_second: Option<u64>,
     pub capacity: Option<u64>,
+    pub limiter_type: Option<RateLimiterType>,
+}
+
+impl RateLimitParams {
+    /// Removing a limiter, raising its rate, loosening its type or setting a capacity above the
+    /// tokens currently in the bucket (set_capacity refills it) loosens the limit.
+    pub fn loosens(params: &Option<RateLimitParams>, rate_limiter: &Option<RateLimiter>) -> bool {
+        match (params, rate_limiter) {
+            (None, current) => current.is_some(),
+            (Some(_), None) => false,
+            (Some(params), Some(limiter)) => {
+                params.capacity.is_some_and(|capacity| capacity > limiter.tokens)
+                    || params.refill_per_second.is_some_and(|rate| rate > limiter.refill_per_second)
+                    || params
+                        .limiter_type
+                        .as_ref()
+                        .is_some_and(|limiter_type| limiter_type.loosens(&limiter.limiter_type))
+            },
+        }
+    }
+
+    /// Every provided value has to be strictly lower than the current one. Any limit is more
+    /// restrictive than no limiter at all.
+    pub fn tightens(&self, rate_limiter: &Option<RateLimiter>) -> bool {
+        if self.capacity.is_none()
+            && self.refill_per_second.is_none()
+            && self.limiter_type.is_none()
+        {
+            return false;
+        }
+        match rate_limiter {
+            None => true,
+            Some(limiter) => {
+                self.capacity.filter(|capacity| *capacity >= limiter.capacity).is_none()
+                    && self
+                        .refill_per_second
+                        .filter(|rate| *rate >= limiter.refill_per_second)
+                        .is_none()
+                    && self
+                        .limiter_type
+                        .as_ref()
+                        .filter(|limiter_type| match (limiter_type, &limiter.limiter_type) {
+                            (
+                                RateLimiterType::Window { window },
+                                RateLimiterType::Window { window: current },
+                            ) => window <= current,
+                            _ => true,
+                        })
+                        .is_none()
+            },
+        }
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CircuitBreakerParams {
+    pub threshold: u64,
+    pub window: u64, // in seconds
 }
@@ -1,11 +1,11 @@
 use crate::*;
-use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[derive(Accounts)]
 pub struct WithdrawFee<'info> {
     pub admin: Signer<'info>,
     #[account(
-        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
         bump = oft_store.bump,
         has_one = admin @OFTError::Unauthorized
     )]
@@ -32,18 +32,26 @@ pub struct WithdrawFee<'info> {
     pub token_program: Interface<'info, TokenInterface>,
 }
 
-impl WithdrawFee<'_> {
-    pub fn apply(ctx: &mut Context<WithdrawFee>, params: &WithdrawFeeParams) -> Result<()> {
+impl<'info> WithdrawFee<'info> {
+    pub fn apply(
+        ctx: &mut Context<'_, '_, '_, 'info, WithdrawFee<'info>>,
+        params: &WithdrawFeeParams,
+    ) -> Result<()> {
         require!(
             ctx.accounts.token_escrow.amount - ctx.accounts.oft_store.tvl_ld >= params.fee_ld,
             OFTError::InvalidFee
         );
         let seeds: &[&[u8]] = &[
             OFT_SEED,
-            &ctx.accounts.token_escrow.key().to_bytes(),
+            &ctx.accounts.oft_store.escrow_seed.to_bytes(),
             &[ctx.accounts.oft_store.bump],
         ];
-        token_interface::transfer_checked(
+        // the remaining accounts are the transfer hook accounts, if any
+        let (hook_accounts, _) = transfer_hook::split_accounts(
+            &ctx.accounts.token_mint.to_account_info(),
+            ctx.remaining_accounts,
+        )?;
+        transfer_hook::transfer_checked(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 TransferChecked {
@@ -56,6 +64,7 @@ impl WithdrawFee<'_> {
             .with_signer(&[&seeds]),
             params.fee_ld,
             ctx.accounts.token_mint.decimals,
+            hook_accounts,
         )?;
         Ok(())
     }
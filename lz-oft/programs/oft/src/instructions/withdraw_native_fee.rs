@@ -0,0 +1,56 @@
+use crate::*;
+use anchor_lang::system_program;
+
+#[derive(Accounts)]
+pub struct WithdrawNativeFee<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    /// CHECK: receives the lamports
+    #[account(mut)]
+    pub native_dest: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl WithdrawNativeFee<'_> {
+    pub fn apply(
+        ctx: &mut Context<WithdrawNativeFee>,
+        params: &WithdrawNativeFeeParams,
+    ) -> Result<()> {
+        // the vault keeps the rent-exempt minimum so that small fees can still be deposited
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(0);
+        require!(
+            ctx.accounts.fee_vault.lamports().saturating_sub(rent_exempt_lamports) >= params.amount,
+            OFTError::InvalidFee
+        );
+        let oft_store_key = ctx.accounts.oft_store.key();
+        let seeds: &[&[u8]] = &[FEE_VAULT_SEED, oft_store_key.as_ref(), &[ctx.bumps.fee_vault]];
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.fee_vault.to_account_info(),
+                    to: ctx.accounts.native_dest.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+            params.amount,
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawNativeFeeParams {
+    pub amount: u64,
+}
@@ -0,0 +1,62 @@
+use crate::*;
+use anchor_lang::system_program;
+
+/// The rent vault pays back the rent of the ATAs created on receive, when the peer charges an
+/// ata_rent_fee_ld. The admin funds it with a plain transfer, and withdraws what is left here.
+#[derive(Accounts)]
+pub struct WithdrawRentVault<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.escrow_seed.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [RENT_VAULT_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub rent_vault: SystemAccount<'info>,
+    /// CHECK: receives the lamports
+    #[account(mut)]
+    pub native_dest: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl WithdrawRentVault<'_> {
+    pub fn apply(
+        ctx: &mut Context<WithdrawRentVault>,
+        params: &WithdrawRentVaultParams,
+    ) -> Result<()> {
+        require!(
+            rent_vault_spare_lamports(ctx.accounts.rent_vault.lamports(), &Rent::get()?)
+                >= params.amount,
+            OFTError::InvalidFee
+        );
+        let oft_store_key = ctx.accounts.oft_store.key();
+        let seeds: &[&[u8]] = &[RENT_VAULT_SEED, oft_store_key.as_ref(), &[ctx.bumps.rent_vault]];
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.rent_vault.to_account_info(),
+                    to: ctx.accounts.native_dest.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+            params.amount,
+        )?;
+        Ok(())
+    }
+}
+
+/// Returns the lamports the rent vault can pay out, as it keeps the rent-exempt minimum.
+pub fn rent_vault_spare_lamports(rent_vault_lamports: u64, rent: &Rent) -> u64 {
+    rent_vault_lamports.saturating_sub(rent.minimum_balance(0))
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawRentVaultParams {
+    pub amount: u64,
+}
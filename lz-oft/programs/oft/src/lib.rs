@@ -6,6 +6,7 @@ pub mod events;
 pub mod instructions;
 pub mod msg_codec;
 pub mod state;
+pub mod transfer_hook;
 
 use errors::*;
 use events::*;
@@ -25,6 +26,13 @@ declare_id!(Pubkey::new_from_array(program_id_from_env!(
 pub const OFT_SEED: &[u8] = b"OFT";
 pub const PEER_SEED: &[u8] = b"Peer";
 pub const ENFORCED_OPTIONS_SEED: &[u8] = b"EnforcedOptions";
+pub const PENDING_CHANGE_SEED: &[u8] = b"PendingChange";
+pub const FEE_EXEMPTION_SEED: &[u8] = b"FeeExemption";
+pub const FEE_DISTRIBUTION_SEED: &[u8] = b"FeeDistribution";
+pub const FEE_VAULT_SEED: &[u8] = b"FeeVault";
+pub const SENDER_RATE_LIMIT_SEED: &[u8] = b"SenderRateLimit";
+pub const NATIVE_SOL_SEED: &[u8] = b"NativeSol";
+pub const RENT_VAULT_SEED: &[u8] = b"RentVault";
 pub const LZ_RECEIVE_TYPES_SEED: &[u8] = oapp::LZ_RECEIVE_TYPES_SEED;
 
 #[program]
@@ -39,6 +47,13 @@ pub mod oft {
         InitOFT::apply(&mut ctx, &params)
     }
 
+    pub fn init_native_oft<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, InitNativeOFT<'info>>,
+        params: InitNativeOFTParams,
+    ) -> Result<()> {
+        InitNativeOFT::apply(&mut ctx, &params)
+    }
+
     // ============================== Admin ==============================
     pub fn set_oft_config(
         mut ctx: Context<SetOFTConfig>,
@@ -58,28 +73,147 @@ pub mod oft {
         SetPause::apply(&mut ctx, &params)
     }
 
-    pub fn withdraw_fee(mut ctx: Context<WithdrawFee>, params: WithdrawFeeParams) -> Result<()> {
+    pub fn withdraw_fee<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, WithdrawFee<'info>>,
+        params: WithdrawFeeParams,
+    ) -> Result<()> {
         WithdrawFee::apply(&mut ctx, &params)
     }
 
+    pub fn add_fee_exemption(
+        mut ctx: Context<AddFeeExemption>,
+        params: AddFeeExemptionParams,
+    ) -> Result<()> {
+        AddFeeExemption::apply(&mut ctx, &params)
+    }
+
+    pub fn remove_fee_exemption(mut ctx: Context<RemoveFeeExemption>) -> Result<()> {
+        RemoveFeeExemption::apply(&mut ctx)
+    }
+
+    pub fn withdraw_native_fee(
+        mut ctx: Context<WithdrawNativeFee>,
+        params: WithdrawNativeFeeParams,
+    ) -> Result<()> {
+        WithdrawNativeFee::apply(&mut ctx, &params)
+    }
+
+    pub fn withdraw_rent_vault(
+        mut ctx: Context<WithdrawRentVault>,
+        params: WithdrawRentVaultParams,
+    ) -> Result<()> {
+        WithdrawRentVault::apply(&mut ctx, &params)
+    }
+
+    pub fn set_fee_distribution(
+        mut ctx: Context<SetFeeDistribution>,
+        params: SetFeeDistributionParams,
+    ) -> Result<()> {
+        SetFeeDistribution::apply(&mut ctx, &params)
+    }
+
+    pub fn queue_change(mut ctx: Context<QueueChange>, params: QueueChangeParams) -> Result<()> {
+        QueueChange::apply(&mut ctx, &params)
+    }
+
+    pub fn cancel_change(mut ctx: Context<CancelChange>) -> Result<()> {
+        CancelChange::apply(&mut ctx)
+    }
+
+    pub fn guard(mut ctx: Context<Guard>, params: GuardParams) -> Result<()> {
+        Guard::apply(&mut ctx, &params)
+    }
+
+    pub fn migrate_oft_type(mut ctx: Context<MigrateOFTType>) -> Result<()> {
+        MigrateOFTType::apply(&mut ctx)
+    }
+
+    pub fn migrate_escrow<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, MigrateEscrow<'info>>,
+    ) -> Result<()> {
+        MigrateEscrow::apply(&mut ctx)
+    }
+
     // ============================== Public ==============================
 
+    pub fn init_sender_rate_limit(
+        mut ctx: Context<InitSenderRateLimit>,
+        params: InitSenderRateLimitParams,
+    ) -> Result<()> {
+        InitSenderRateLimit::apply(&mut ctx, &params)
+    }
+
+    pub fn migrate_account(
+        mut ctx: Context<MigrateAccount>,
+        params: MigrateAccountParams,
+    ) -> Result<()> {
+        MigrateAccount::apply(&mut ctx, &params)
+    }
+
+    pub fn init_fee_vault(mut ctx: Context<InitFeeVault>) -> Result<()> {
+        InitFeeVault::apply(&mut ctx)
+    }
+
+    pub fn set_lz_receive_types_accounts(
+        mut ctx: Context<SetLzReceiveTypesAccounts>,
+    ) -> Result<()> {
+        SetLzReceiveTypesAccounts::apply(&mut ctx)
+    }
+
     pub fn quote_oft(ctx: Context<QuoteOFT>, params: QuoteOFTParams) -> Result<QuoteOFTResult> {
         QuoteOFT::apply(&ctx, &params)
     }
 
+    pub fn quote_receive(
+        ctx: Context<QuoteReceive>,
+        params: QuoteReceiveParams,
+    ) -> Result<QuoteReceiveResult> {
+        QuoteReceive::apply(&ctx, &params)
+    }
+
     pub fn quote_send(ctx: Context<QuoteSend>, params: QuoteSendParams) -> Result<MessagingFee> {
         QuoteSend::apply(&ctx, &params)
     }
 
-    pub fn send(
-        mut ctx: Context<Send>,
+    pub fn pause_status(
+        ctx: Context<PauseStatus>,
+        params: PauseStatusParams,
+    ) -> Result<PauseStatusResult> {
+        PauseStatus::apply(&ctx, &params)
+    }
+
+    pub fn distribute_fees<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, DistributeFees<'info>>,
+    ) -> Result<()> {
+        DistributeFees::apply(&mut ctx)
+    }
+
+    pub fn oft_status(ctx: Context<OFTStatus>, params: OFTStatusParams) -> Result<OFTStatusResult> {
+        OFTStatus::apply(&ctx, &params)
+    }
+
+    pub fn peer_stats(ctx: Context<PeerStats>, params: PeerStatsParams) -> Result<PeerStatsResult> {
+        PeerStats::apply(&ctx, &params)
+    }
+
+    pub fn rate_limit_status(
+        ctx: Context<RateLimitStatus>,
+        params: RateLimitStatusParams,
+    ) -> Result<RateLimitStatusResult> {
+        RateLimitStatus::apply(&ctx, &params)
+    }
+
+    pub fn send<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, Send<'info>>,
         params: SendParams,
     ) -> Result<(MessagingReceipt, OFTReceipt)> {
         Send::apply(&mut ctx, &params)
     }
 
-    pub fn lz_receive(mut ctx: Context<LzReceive>, params: LzReceiveParams) -> Result<()> {
+    pub fn lz_receive<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, LzReceive<'info>>,
+        params: LzReceiveParams,
+    ) -> Result<()> {
         LzReceive::apply(&mut ctx, &params)
     }
 
@@ -89,6 +223,10 @@ pub mod oft {
     ) -> Result<Vec<oapp::endpoint_cpi::LzAccount>> {
         LzReceiveTypes::apply(&ctx, &params)
     }
+
+    pub fn execute_change(mut ctx: Context<ExecuteChange>) -> Result<()> {
+        ExecuteChange::apply(&mut ctx)
+    }
 }
 
 #[derive(Accounts)]
@@ -3,7 +3,15 @@ use crate::*;
 const SEND_TO_OFFSET: usize = 0;
 const SEND_AMOUNT_SD_OFFSET: usize = 32;
 const COMPOSE_MSG_OFFSET: usize = 40;
+// A compose msg starts with the 32 bytes compose_from, so a message of 41 bytes has no compose msg
+// and its last byte carries the flags.
+const FLAGS_OFFSET: usize = 40;
+const FLAGGED_MSG_LEN: usize = 41;
 
+pub const FLAG_TO_TOKEN_ACCOUNT: u8 = 1; // send_to is a token account, not a wallet
+
+// The flags are only understood by peers that enable PeerConfig.token_account_dest. An EVM peer
+// would decode a flagged message as a composed one.
 pub fn encode(
     send_to: [u8; 32],
     amount_sd: u64,
@@ -25,6 +33,14 @@ pub fn encode(
     }
 }
 
+pub fn encode_to_token_account(send_to: [u8; 32], amount_sd: u64) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(FLAGGED_MSG_LEN); // 32 + 8 + 1
+    encoded.extend_from_slice(&send_to);
+    encoded.extend_from_slice(&amount_sd.to_be_bytes());
+    encoded.push(FLAG_TO_TOKEN_ACCOUNT);
+    encoded
+}
+
 pub fn send_to(message: &[u8]) -> [u8; 32] {
     let mut send_to = [0; 32];
     send_to.copy_from_slice(&message[SEND_TO_OFFSET..SEND_AMOUNT_SD_OFFSET]);
@@ -37,8 +53,12 @@ pub fn amount_sd(message: &[u8]) -> u64 {
     u64::from_be_bytes(amount_sd_bytes)
 }
 
+pub fn is_to_token_account(message: &[u8]) -> bool {
+    message.len() == FLAGGED_MSG_LEN && message[FLAGS_OFFSET] & FLAG_TO_TOKEN_ACCOUNT != 0
+}
+
 pub fn compose_msg(message: &[u8]) -> Option<Vec<u8>> {
-    if message.len() > COMPOSE_MSG_OFFSET {
+    if message.len() > COMPOSE_MSG_OFFSET && message.len() != FLAGGED_MSG_LEN {
         Some(message[COMPOSE_MSG_OFFSET..].to_vec())
     } else {
         None
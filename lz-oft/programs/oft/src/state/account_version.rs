@@ -0,0 +1,74 @@
+use crate::*;
+use anchor_lang::Discriminator;
+
+/// The OFTStore and the PeerConfig start with a version byte. Accounts created before it was added
+/// have the layout of version 0, which is recognized by its size: they are read into the current
+/// struct with the fields added since at their defaults, and `version` set to 0. As a write would
+/// not fit the legacy account, it fails with AccountNotMigrated until `migrate_account` has
+/// reallocated the account and rewritten it in the current layout.
+///
+/// The cutover is deliberate: reallocating on the first write would make the executor pay for the
+/// larger accounts in lz_receive. The lz:oft:solana:migrate-accounts task migrates the oft_store
+/// and its peers right after the upgrade, and messages that failed in between can be retried.
+pub const ACCOUNT_VERSION: u8 = 1;
+
+/// Implements the account traits of #[account] for a versioned account, reading the accounts of
+/// the size of `$legacy` as that layout.
+macro_rules! versioned_account {
+    ($account:ident, $legacy:ident, $discriminator:expr) => {
+        impl Discriminator for $account {
+            const DISCRIMINATOR: [u8; 8] = $discriminator;
+        }
+
+        impl Owner for $account {
+            fn owner() -> Pubkey {
+                crate::ID
+            }
+        }
+
+        impl AccountSerialize for $account {
+            fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+                require!(self.version == ACCOUNT_VERSION, OFTError::AccountNotMigrated);
+                if writer.write_all(&Self::DISCRIMINATOR).is_err() {
+                    return Err(ErrorCode::AccountDidNotSerialize.into());
+                }
+                if AnchorSerialize::serialize(self, writer).is_err() {
+                    return Err(ErrorCode::AccountDidNotSerialize.into());
+                }
+                Ok(())
+            }
+        }
+
+        impl AccountDeserialize for $account {
+            fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+                if buf.len() < Self::DISCRIMINATOR.len() {
+                    return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+                }
+                if buf[..8] != Self::DISCRIMINATOR {
+                    return Err(error!(ErrorCode::AccountDiscriminatorMismatch)
+                        .with_account_name(stringify!($account)));
+                }
+                let account = Self::try_deserialize_unchecked(buf)?;
+                // an account of the current size must have been written at the current version
+                if account.version != ACCOUNT_VERSION && buf.len() != 8 + $legacy::INIT_SPACE {
+                    return Err(ErrorCode::AccountDidNotDeserialize.into());
+                }
+                Ok(account)
+            }
+
+            fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+                let mut data: &[u8] = &buf[8..];
+                let account = if buf.len() == 8 + $legacy::INIT_SPACE {
+                    $legacy::deserialize(&mut data).map(Into::into)
+                } else {
+                    AnchorDeserialize::deserialize(&mut data)
+                };
+                account.map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+            }
+        }
+    };
+}
+
+// sha256("account:<name>")[..8], as derived by #[account]
+versioned_account!(OFTStore, OFTStoreV0, [195, 215, 104, 134, 185, 195, 240, 114]);
+versioned_account!(PeerConfig, PeerConfigV0, [181, 157, 86, 198, 33, 193, 94, 203]);
@@ -0,0 +1,42 @@
+use crate::*;
+
+pub const FEE_DISTRIBUTION_MAX_RECIPIENTS: usize = 8;
+
+/// FeeDistribution splits the fees accrued in the token_escrow across the recipients
+/// proportionally to their weights.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeDistribution {
+    pub oft_store: Pubkey,
+    #[max_len(FEE_DISTRIBUTION_MAX_RECIPIENTS)]
+    pub recipients: Vec<FeeRecipient>,
+    pub total_distributed_ld: u64, // lifetime fees paid out by distribute_fees
+    pub bump: u8,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FeeRecipient {
+    pub token_account: Pubkey,
+    pub weight: u16,
+}
+
+impl FeeDistribution {
+    pub fn is_valid(recipients: &[FeeRecipient], token_escrow: Pubkey) -> bool {
+        !recipients.is_empty()
+            && recipients.len() <= FEE_DISTRIBUTION_MAX_RECIPIENTS
+            && recipients
+                .iter()
+                .all(|recipient| recipient.weight > 0 && recipient.token_account != token_escrow)
+    }
+
+    /// Returns the share of each recipient. The rounding remainder stays in the escrow.
+    pub fn split(&self, amount_ld: u64) -> Vec<u64> {
+        let total_weight: u128 = self.recipients.iter().map(|r| r.weight as u128).sum();
+        self.recipients
+            .iter()
+            .map(|recipient| {
+                ((amount_ld as u128) * (recipient.weight as u128) / total_weight) as u64
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,11 @@
+use crate::*;
+
+/// FeeExemption marks a sender that can send without paying the OFT fee.
+/// It is derived from the OFTStore and the sender, so it only has to exist to take effect.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeExemption {
+    pub oft_store: Pubkey,
+    pub sender: Pubkey,
+    pub bump: u8,
+}
@@ -1,5 +1,15 @@
+pub mod account_version;
+pub mod fee_distribution;
+pub mod fee_exemption;
 pub mod oft;
 pub mod peer_config;
+pub mod pending_change;
+pub mod sender_rate_limit;
 
+pub use account_version::*;
+pub use fee_distribution::*;
+pub use fee_exemption::*;
 pub use oft::*;
 pub use peer_config::*;
+pub use pending_change::*;
+pub use sender_rate_limit::*;
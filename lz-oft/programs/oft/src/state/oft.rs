@@ -1,23 +1,84 @@
 use crate::*;
 
-#[account]
-#[derive(InitSpace)]
+/// The account traits are implemented in account_version, which also reads the OFTStoreV0 layout.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
 pub struct OFTStore {
+    pub version: u8, // ACCOUNT_VERSION
     // immutable
     pub oft_type: OFTType,
     pub ld2sd_rate: u64,
     pub token_mint: Pubkey,
-    pub token_escrow: Pubkey, // this account is used to hold TVL and fees
+    pub token_escrow: Pubkey, // this account is used to hold TVL and fees, it can be migrated
     pub endpoint_program: Pubkey,
     pub bump: u8,
     // mutable
     pub tvl_ld: u64, // total value locked. if oft_type is Native, it is always 0.
+    pub total_fee_ld: u64, // lifetime OFT fees collected into the token_escrow
     // configurable
     pub admin: Pubkey,
     pub default_fee_bps: u16,
+    pub max_referral_fee_bps: u16, // max share of the OFT fee a sender can divert to a referrer
+    pub default_oft_limits: OFTLimits,
     pub paused: bool,
+    pub paused_until: Option<i64>, // a pause with an expiry lifts itself, otherwise the unpauser lifts it
     pub pauser: Option<Pubkey>,
     pub unpauser: Option<Pubkey>,
+    pub guardian: Option<Pubkey>,
+    pub timelock_delay: u64, // in seconds. 0 means risky changes take effect immediately
+    pub change_nonce: u64,   // nonce of the next PendingChange
+    // aggregate limits across all peers, consumed alongside the per-peer ones
+    pub outbound_rate_limiter: Option<RateLimiter>,
+    pub inbound_rate_limiter: Option<RateLimiter>,
+    pub native_sol: bool, // the adapter of the native mint sends and receives lamports
+    pub escrow_seed: Pubkey, // the initial token_escrow, which the oft_store address is derived from
+}
+
+/// The layout of the OFTStore before the version byte was added.
+#[derive(AnchorDeserialize, InitSpace)]
+pub(crate) struct OFTStoreV0 {
+    pub oft_type: OFTType,
+    pub ld2sd_rate: u64,
+    pub token_mint: Pubkey,
+    pub token_escrow: Pubkey,
+    pub endpoint_program: Pubkey,
+    pub bump: u8,
+    pub tvl_ld: u64,
+    pub admin: Pubkey,
+    pub default_fee_bps: u16,
+    pub paused: bool,
+    pub pauser: Option<Pubkey>,
+    pub unpauser: Option<Pubkey>,
+}
+
+impl From<OFTStoreV0> for OFTStore {
+    fn from(oft_store: OFTStoreV0) -> Self {
+        OFTStore {
+            version: 0,
+            oft_type: oft_store.oft_type,
+            ld2sd_rate: oft_store.ld2sd_rate,
+            token_mint: oft_store.token_mint,
+            token_escrow: oft_store.token_escrow,
+            endpoint_program: oft_store.endpoint_program,
+            bump: oft_store.bump,
+            tvl_ld: oft_store.tvl_ld,
+            total_fee_ld: 0,
+            admin: oft_store.admin,
+            default_fee_bps: oft_store.default_fee_bps,
+            max_referral_fee_bps: 0,
+            default_oft_limits: OFTLimits::default(),
+            paused: oft_store.paused,
+            paused_until: None,
+            pauser: oft_store.pauser,
+            unpauser: oft_store.unpauser,
+            guardian: None,
+            timelock_delay: 0,
+            change_nonce: 0,
+            outbound_rate_limiter: None,
+            inbound_rate_limiter: None,
+            native_sol: false,
+            escrow_seed: oft_store.token_escrow,
+        }
+    }
 }
 
 #[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
@@ -27,6 +88,11 @@ pub enum OFTType {
 }
 
 impl OFTStore {
+    pub fn is_native_mint(token_mint: &Pubkey) -> bool {
+        *token_mint == anchor_spl::token::spl_token::native_mint::ID
+            || *token_mint == anchor_spl::token_2022::spl_token_2022::native_mint::ID
+    }
+
     pub fn ld2sd(&self, amount_ld: u64) -> u64 {
         amount_ld / self.ld2sd_rate
     }
@@ -38,6 +104,18 @@ impl OFTStore {
     pub fn remove_dust(&self, amount_ld: u64) -> u64 {
         amount_ld - amount_ld % self.ld2sd_rate
     }
+
+    pub fn is_timelocked(&self) -> bool {
+        self.timelock_delay > 0
+    }
+
+    pub fn is_paused(&self, now: i64) -> bool {
+        is_pause_active(self.paused, self.paused_until, now)
+    }
+}
+
+pub fn is_pause_active(paused: bool, paused_until: Option<i64>, now: i64) -> bool {
+    paused && paused_until.filter(|until| now >= *until).is_none()
 }
 
 /// LzReceiveTypesAccounts includes accounts that are used in the LzReceiveTypes
@@ -47,4 +125,5 @@ impl OFTStore {
 pub struct LzReceiveTypesAccounts {
     pub oft_store: Pubkey,
     pub token_mint: Pubkey,
+    pub extra_account_metas: Pubkey, // of the transfer hook, or this program if the mint has none
 }
@@ -2,56 +2,317 @@ use crate::*;
 
 pub const ENFORCED_OPTIONS_SEND_MAX_LEN: usize = 512;
 pub const ENFORCED_OPTIONS_SEND_AND_CALL_MAX_LEN: usize = 1024;
+pub const FEE_SCHEDULE_MAX_TIERS: usize = 8;
+pub const CIRCUIT_BREAKER_MAX_APPROVED_GUIDS: usize = 8;
 
-#[account]
-#[derive(InitSpace)]
+/// The account traits are implemented in account_version, which also reads the PeerConfigV0 layout.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
 pub struct PeerConfig {
+    pub version: u8, // ACCOUNT_VERSION
     pub peer_address: [u8; 32],
     pub enforced_options: EnforcedOptions,
     pub outbound_rate_limiter: Option<RateLimiter>,
     pub inbound_rate_limiter: Option<RateLimiter>,
     pub fee_bps: Option<u16>,
     pub bump: u8,
+    pub outbound_paused: bool,
+    pub outbound_paused_until: Option<i64>,
+    pub inbound_paused: bool,
+    pub inbound_paused_until: Option<i64>,
+    pub inbound_circuit_breaker: Option<CircuitBreaker>,
+    pub oft_limits: Option<OFTLimits>, // if None, the default_oft_limits of the OFTStore apply
+    pub fee_schedule: Option<FeeSchedule>, // if set, it replaces fee_bps
+    pub native_fee: Option<u64>,       // in lamports. if set, it replaces the token fee
+    pub stats: TransferStats,
+    pub sender_rate_limit: Option<SenderRateLimitConfig>, // if set, every sender has its own bucket
+    pub ata_rent_fee_ld: Option<u64>, // deducted on receive when the recipient ATA is created
+    pub token_account_dest: bool,     // the peer decodes msg_codec::FLAG_TO_TOKEN_ACCOUNT
 }
 
+/// The layout of the PeerConfig before the version byte was added.
+#[derive(AnchorDeserialize, InitSpace)]
+pub(crate) struct PeerConfigV0 {
+    pub peer_address: [u8; 32],
+    pub enforced_options: EnforcedOptions,
+    pub outbound_rate_limiter: Option<RateLimiterV0>,
+    pub inbound_rate_limiter: Option<RateLimiterV0>,
+    pub fee_bps: Option<u16>,
+    pub bump: u8,
+}
+
+impl From<PeerConfigV0> for PeerConfig {
+    fn from(peer: PeerConfigV0) -> Self {
+        PeerConfig {
+            version: 0,
+            peer_address: peer.peer_address,
+            enforced_options: peer.enforced_options,
+            outbound_rate_limiter: peer.outbound_rate_limiter.map(Into::into),
+            inbound_rate_limiter: peer.inbound_rate_limiter.map(Into::into),
+            fee_bps: peer.fee_bps,
+            bump: peer.bump,
+            outbound_paused: false,
+            outbound_paused_until: None,
+            inbound_paused: false,
+            inbound_paused_until: None,
+            inbound_circuit_breaker: None,
+            oft_limits: None,
+            fee_schedule: None,
+            native_fee: None,
+            stats: TransferStats::default(),
+            sender_rate_limit: None,
+            ata_rent_fee_ld: None,
+            token_account_dest: false,
+        }
+    }
+}
+
+impl PeerConfig {
+    pub fn is_outbound_paused(&self, now: i64) -> bool {
+        is_pause_active(self.outbound_paused, self.outbound_paused_until, now)
+    }
+
+    pub fn is_inbound_paused(&self, now: i64) -> bool {
+        is_pause_active(self.inbound_paused, self.inbound_paused_until, now)
+    }
+
+    pub fn get_oft_limits(&self, oft_store: &OFTStore) -> OFTLimits {
+        self.oft_limits.clone().unwrap_or_else(|| oft_store.default_oft_limits.clone())
+    }
+
+    /// Whether a send may deliver to a token account, which is flagged in the message. Only peers
+    /// that decode the flag accept it, and a flagged message cannot carry a compose msg.
+    pub fn accepts_to_token_account(&self, compose_msg: &Option<Vec<u8>>) -> bool {
+        self.token_account_dest && compose_msg.is_none()
+    }
+
+    /// The fee deducted when the ATA of the recipient is created, if the rent vault pays the rent
+    /// back to the payer. It is only charged in full: a smaller amount is delivered without it.
+    /// lz_receive_types can not tell whether it applies, and resolves the transfer hook accounts
+    /// with the full amount, so an adapter can only charge it if the extra account metas of its
+    /// transfer hook do not depend on the amount.
+    pub fn get_ata_rent_fee_ld(&self, amount_ld: u64) -> u64 {
+        match self.ata_rent_fee_ld {
+            Some(fee_ld) if fee_ld <= amount_ld => fee_ld,
+            _ => 0,
+        }
+    }
+}
+
+/// SenderRateLimitConfig is applied to the SenderRateLimit bucket of every sender of the peer.
 #[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct SenderRateLimitConfig {
+    pub capacity: u64,
+    pub refill_per_second: u64,
+}
+
+impl SenderRateLimitConfig {
+    pub fn new_rate_limiter(&self, current_time: u64) -> RateLimiter {
+        let mut rate_limiter = RateLimiter::default();
+        rate_limiter.set_capacity(self.capacity, current_time);
+        rate_limiter.refill_per_second = self.refill_per_second;
+        rate_limiter
+    }
+
+    /// Applies config changes to an existing bucket. Unlike SetPeerConfig, a new capacity never
+    /// refills the bucket.
+    pub fn sync(&self, rate_limiter: &mut RateLimiter, current_time: u64) {
+        if rate_limiter.refill_per_second != self.refill_per_second {
+            rate_limiter.set_rate(self.refill_per_second, current_time);
+        }
+        if rate_limiter.capacity != self.capacity {
+            rate_limiter.refill(0, current_time);
+            rate_limiter.capacity = self.capacity;
+            rate_limiter.tokens = std::cmp::min(rate_limiter.tokens, self.capacity);
+        }
+    }
+
+    pub fn loosens(
+        config: &Option<SenderRateLimitConfig>,
+        current: &Option<SenderRateLimitConfig>,
+    ) -> bool {
+        match (config, current) {
+            (None, current) => current.is_some(),
+            (Some(_), None) => false,
+            (Some(config), Some(current)) => {
+                config.capacity > current.capacity
+                    || config.refill_per_second > current.refill_per_second
+            },
+        }
+    }
+}
+
+/// Lifetime transfer statistics of a peer. The counters saturate instead of failing transfers.
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct TransferStats {
+    pub total_sent_ld: u64, // amount credited to the remote chain, excluding fees
+    pub total_received_ld: u64, // paid to recipients, net of ATA rent fees, before token2022 fees
+    pub sent_count: u64,
+    pub received_count: u64,
+    pub total_fee_ld: u64, // OFT fees collected into the token_escrow
+}
+
+impl TransferStats {
+    pub fn record_sent(&mut self, amount_ld: u64, fee_ld: u64) {
+        self.total_sent_ld = self.total_sent_ld.saturating_add(amount_ld);
+        self.sent_count = self.sent_count.saturating_add(1);
+        self.total_fee_ld = self.total_fee_ld.saturating_add(fee_ld);
+    }
+
+    pub fn record_received(&mut self, amount_ld: u64) {
+        self.total_received_ld = self.total_received_ld.saturating_add(amount_ld);
+        self.received_count = self.received_count.saturating_add(1);
+    }
+}
+
+#[derive(Clone, Default, Debug, AnchorSerialize, AnchorDeserialize, InitSpace)]
 pub struct RateLimiter {
     pub capacity: u64,
     pub tokens: u64,
     pub refill_per_second: u64,
     pub last_refill_time: u64,
+    pub limiter_type: RateLimiterType, // the limiters of legacy accounts read as TokenBucket
+}
+
+/// The layout of the RateLimiter before limiter_type was added, always a token bucket.
+#[derive(AnchorDeserialize, InitSpace)]
+pub(crate) struct RateLimiterV0 {
+    pub capacity: u64,
+    pub tokens: u64,
+    pub refill_per_second: u64,
+    pub last_refill_time: u64,
+}
+
+impl From<RateLimiterV0> for RateLimiter {
+    fn from(rate_limiter: RateLimiterV0) -> Self {
+        RateLimiter {
+            capacity: rate_limiter.capacity,
+            tokens: rate_limiter.tokens,
+            refill_per_second: rate_limiter.refill_per_second,
+            last_refill_time: rate_limiter.last_refill_time,
+            limiter_type: RateLimiterType::TokenBucket,
+        }
+    }
 }
 
+/// TokenBucket refills `refill_per_second` tokens every second. Window matches the EVM OFT
+/// limiter: the amount in flight (capacity - tokens) decays linearly by `capacity` every `window`
+/// seconds, and refill_per_second is ignored.
+#[derive(Clone, Default, Debug, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq)]
+pub enum RateLimiterType {
+    #[default]
+    TokenBucket,
+    Window {
+        window: u64, // in seconds
+    },
+}
+
+impl RateLimiterType {
+    pub fn is_valid(&self) -> bool {
+        !matches!(self, RateLimiterType::Window { window: 0 })
+    }
+
+    /// Switching types, or shortening the window, lets more tokens through.
+    pub fn loosens(&self, current: &RateLimiterType) -> bool {
+        match (self, current) {
+            (RateLimiterType::TokenBucket, RateLimiterType::TokenBucket) => false,
+            (RateLimiterType::Window { window }, RateLimiterType::Window { window: current }) => {
+                window < current
+            },
+            _ => true,
+        }
+    }
+}
+
+/// The limiter logic is pure: every method takes the current time in seconds, which on-chain
+/// callers read with `RateLimiter::now`.
 impl RateLimiter {
-    pub fn set_rate(&mut self, refill_per_second: u64) -> Result<()> {
-        self.refill(0)?;
-        self.refill_per_second = refill_per_second;
+    /// Reads the current time from the Clock sysvar. A negative timestamp is rejected.
+    pub fn now() -> Result<u64> {
+        u64::try_from(Clock::get()?.unix_timestamp).map_err(|_| error!(OFTError::InvalidTimestamp))
+    }
+
+    /// Switches the type, keeping the tokens currently available so that the amount in flight
+    /// carries over.
+    pub fn set_limiter_type(
+        &mut self,
+        limiter_type: RateLimiterType,
+        current_time: u64,
+    ) -> Result<()> {
+        require!(limiter_type.is_valid(), OFTError::InvalidRateLimiter);
+        self.refill(0, current_time);
+        self.limiter_type = limiter_type;
         Ok(())
     }
 
-    pub fn set_capacity(&mut self, capacity: u64) -> Result<()> {
+    /// Tokens accrued at the previous rate are kept.
+    pub fn set_rate(&mut self, refill_per_second: u64, current_time: u64) {
+        self.refill(0, current_time);
+        self.refill_per_second = refill_per_second;
+    }
+
+    /// Resets the bucket to full at the new capacity.
+    pub fn set_capacity(&mut self, capacity: u64, current_time: u64) {
         self.capacity = capacity;
         self.tokens = capacity;
-        self.last_refill_time = Clock::get()?.unix_timestamp.try_into().unwrap();
-        Ok(())
+        self.last_refill_time = current_time;
     }
 
-    pub fn refill(&mut self, extra_tokens: u64) -> Result<()> {
-        let mut new_tokens = extra_tokens;
-        let current_time: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
-        if current_time > self.last_refill_time {
-            let time_elapsed_in_seconds = current_time - self.last_refill_time;
-            new_tokens = new_tokens
-                .saturating_add(time_elapsed_in_seconds.saturating_mul(self.refill_per_second));
-        }
-        self.tokens = std::cmp::min(self.capacity, self.tokens.saturating_add(new_tokens));
+    /// Adds the tokens accrued since the last refill plus `extra_tokens`, up to the capacity.
+    /// A current time before the last refill adds no time-based tokens and does not move the
+    /// last refill time back, so the same period is never accrued twice.
+    pub fn refill(&mut self, extra_tokens: u64, current_time: u64) {
+        self.tokens = std::cmp::min(
+            self.capacity,
+            self.projected_tokens(current_time).saturating_add(extra_tokens),
+        );
+        self.last_refill_time = std::cmp::max(self.last_refill_time, current_time);
+    }
 
-        self.last_refill_time = current_time;
-        Ok(())
+    /// Returns the tokens the bucket would hold at `current_time` after refilling.
+    pub fn projected_tokens(&self, current_time: u64) -> u64 {
+        let time_elapsed_in_seconds = current_time.saturating_sub(self.last_refill_time);
+        let new_tokens = self.refill_amount(time_elapsed_in_seconds);
+        std::cmp::min(self.capacity, self.tokens.saturating_add(new_tokens))
+    }
+
+    /// Returns the seconds from `current_time` until the bucket is full, or None if it never
+    /// refills.
+    pub fn seconds_to_full(&self, current_time: u64) -> Option<u64> {
+        // measured from the last refill, so that rounding of the projection does not add up
+        let missing_tokens = self.capacity.saturating_sub(self.tokens);
+        let seconds_from_last_refill = match self.limiter_type {
+            _ if missing_tokens == 0 => 0,
+            RateLimiterType::TokenBucket if self.refill_per_second == 0 => return None,
+            RateLimiterType::TokenBucket => missing_tokens.div_ceil(self.refill_per_second),
+            // missing_tokens * window / capacity, rounded up
+            RateLimiterType::Window { window } => ((missing_tokens as u128) * (window as u128))
+                .div_ceil(self.capacity as u128)
+                .try_into()
+                .unwrap_or(u64::MAX),
+        };
+        Some(
+            seconds_from_last_refill
+                .saturating_sub(current_time.saturating_sub(self.last_refill_time)),
+        )
+    }
+
+    fn refill_amount(&self, time_elapsed_in_seconds: u64) -> u64 {
+        match self.limiter_type {
+            RateLimiterType::TokenBucket => {
+                time_elapsed_in_seconds.saturating_mul(self.refill_per_second)
+            },
+            // capacity * time_elapsed_in_seconds / window
+            RateLimiterType::Window { window } => {
+                let decay =
+                    (self.capacity as u128) * (time_elapsed_in_seconds as u128) / (window as u128);
+                decay.try_into().unwrap_or(u64::MAX)
+            },
+        }
     }
 
-    pub fn try_consume(&mut self, amount: u64) -> Result<()> {
-        self.refill(0)?;
+    /// Refills, then takes `amount` tokens. On failure the limiter is left refilled.
+    pub fn try_consume(&mut self, amount: u64, current_time: u64) -> Result<()> {
+        self.refill(0, current_time);
         match self.tokens.checked_sub(amount) {
             Some(new_tokens) => {
                 self.tokens = new_tokens;
@@ -62,6 +323,103 @@ impl RateLimiter {
     }
 }
 
+/// CircuitBreaker tracks the inbound volume over a sliding window. The volume decays linearly by
+/// `threshold` every `window` seconds, and the breaker trips when the volume would exceed `threshold`.
+/// Messages rejected while it is tripped stay parked at the endpoint. When resuming the peer, the
+/// unpauser approves the guids of the parked messages to deliver: each of them passes once without
+/// being recorded, so the backlog does not trip the breaker again.
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct CircuitBreaker {
+    pub threshold: u64,
+    pub window: u64,
+    pub volume: u64,
+    pub last_update_time: u64,
+    #[max_len(CIRCUIT_BREAKER_MAX_APPROVED_GUIDS)]
+    pub approved_guids: Vec<[u8; 32]>,
+}
+
+impl CircuitBreaker {
+    /// Adds the amount to the volume, or returns false without recording it if the breaker trips.
+    /// An approved message passes once without being recorded.
+    pub fn try_record(&mut self, amount: u64, guid: &[u8; 32], current_time: u64) -> bool {
+        self.decay(current_time);
+        if let Some(index) = self.approved_guids.iter().position(|approved| approved == guid) {
+            self.approved_guids.swap_remove(index);
+            return true;
+        }
+        match self.volume.checked_add(amount) {
+            Some(volume) if volume <= self.threshold => {
+                self.volume = volume;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Rearms the breaker, replacing the approved guids.
+    pub fn resume(&mut self, approved_guids: Vec<[u8; 32]>, current_time: u64) -> Result<()> {
+        require!(
+            approved_guids.len() <= CIRCUIT_BREAKER_MAX_APPROVED_GUIDS,
+            OFTError::InvalidApprovedGuids
+        );
+        self.approved_guids = approved_guids;
+        self.reset(current_time);
+        Ok(())
+    }
+
+    pub fn reset(&mut self, current_time: u64) {
+        self.volume = 0;
+        self.last_update_time = current_time;
+    }
+
+    fn decay(&mut self, current_time: u64) {
+        if current_time > self.last_update_time && self.window > 0 {
+            let time_elapsed_in_seconds = current_time - self.last_update_time;
+            let decay = (self.threshold as u128) * (time_elapsed_in_seconds as u128)
+                / (self.window as u128);
+            self.volume = self.volume.saturating_sub(decay.try_into().unwrap_or(u64::MAX));
+        }
+        self.last_update_time = self.last_update_time.max(current_time);
+    }
+}
+
+/// FeeSchedule charges the fee_bps of the highest tier whose min_amount_ld does not exceed the
+/// amount. The fee is then clamped between min_fee_ld and max_fee_ld, and never exceeds the amount.
+#[derive(Clone, Default, Debug, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FeeSchedule {
+    #[max_len(FEE_SCHEDULE_MAX_TIERS)]
+    pub tiers: Vec<FeeTier>, // sorted by min_amount_ld in ascending order
+    pub min_fee_ld: u64,
+    pub max_fee_ld: u64,
+}
+
+#[derive(Clone, Default, Debug, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FeeTier {
+    pub min_amount_ld: u64,
+    pub fee_bps: u16,
+}
+
+impl FeeSchedule {
+    pub fn is_valid(&self) -> bool {
+        self.tiers.len() <= FEE_SCHEDULE_MAX_TIERS
+            && self.tiers.iter().all(|tier| tier.fee_bps < MAX_FEE_BASIS_POINTS)
+            && self.tiers.windows(2).all(|pair| pair[0].min_amount_ld < pair[1].min_amount_ld)
+            && self.min_fee_ld <= self.max_fee_ld
+    }
+
+    pub fn calculate_fee(&self, amount_ld: u64) -> u64 {
+        let fee_bps = self
+            .tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_amount_ld <= amount_ld)
+            .map_or(0, |tier| tier.fee_bps);
+        // amount_ld * fee_bps / MAX_FEE_BASIS_POINTS
+        let fee = ((amount_ld as u128) * (fee_bps as u128) / (MAX_FEE_BASIS_POINTS as u128)) as u64;
+        fee.clamp(self.min_fee_ld, self.max_fee_ld).min(amount_ld)
+    }
+}
+
 #[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
 pub struct EnforcedOptions {
     #[max_len(ENFORCED_OPTIONS_SEND_MAX_LEN)]
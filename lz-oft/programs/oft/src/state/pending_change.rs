@@ -0,0 +1,69 @@
+use crate::*;
+
+pub const MAX_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60; // 30 days
+
+/// PendingChange holds a risky config change that can only be executed after `execute_after`.
+/// It is closed on execution or cancellation, refunding the rent to `payer`.
+#[account]
+pub struct PendingChange {
+    pub oft_store: Pubkey,
+    pub nonce: u64,
+    pub payer: Pubkey,
+    pub execute_after: i64,
+    pub change: ConfigChange,
+    pub bump: u8,
+}
+
+impl PendingChange {
+    pub fn space(change: &ConfigChange) -> usize {
+        // oft_store + nonce + payer + execute_after + change + bump
+        32 + 8 + 32 + 8 + change.try_to_vec().map_or(0, |bytes| bytes.len()) + 1
+    }
+
+    /// For the changes applied by their own instruction rather than ExecuteChange: when the
+    /// timelock is enabled, the change has to be queued and its delay elapsed. The pending change is
+    /// then closed, refunding the rent to its payer.
+    pub fn consume<'info>(
+        pending_change: &Option<Account<'info, PendingChange>>,
+        payer: &Option<AccountInfo<'info>>,
+        oft_store: &OFTStore,
+        change: &ConfigChange,
+    ) -> Result<()> {
+        let Some(pending_change) = pending_change else {
+            require!(!oft_store.is_timelocked(), OFTError::TimelockRequired);
+            return Ok(());
+        };
+        require!(
+            pending_change.change.try_to_vec()? == change.try_to_vec()?,
+            OFTError::InvalidPendingChange
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= pending_change.execute_after,
+            OFTError::TimelockNotElapsed
+        );
+        let payer = payer
+            .as_ref()
+            .filter(|payer| payer.key() == pending_change.payer)
+            .ok_or(OFTError::InvalidPendingChange)?;
+        pending_change.close(payer.clone())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum ConfigChange {
+    OFTConfig(SetOFTConfigParams),
+    PeerConfig(SetPeerConfigParams),
+    FeeDistribution(SetFeeDistributionParams),
+    // applied by migrate_oft_type and migrate_escrow
+    MigrateOFTType,
+    MigrateEscrow { new_token_escrow: Pubkey },
+}
+
+impl ConfigChange {
+    pub fn remote_eid(&self) -> Option<u32> {
+        match self {
+            ConfigChange::PeerConfig(params) => Some(params.remote_eid),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use crate::*;
+
+/// SenderRateLimit is the outbound bucket of a single sender for a peer. It is consumed in Send
+/// alongside the outbound_rate_limiter of the peer when PeerConfig.sender_rate_limit is set.
+#[account]
+#[derive(InitSpace)]
+pub struct SenderRateLimit {
+    pub rate_limiter: RateLimiter,
+    pub bump: u8,
+}
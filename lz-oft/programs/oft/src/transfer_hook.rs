@@ -0,0 +1,144 @@
+use crate::*;
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        extension::{transfer_hook, StateWithExtensions},
+        onchain::invoke_transfer_checked,
+        state::Mint as MintState,
+    },
+    token_interface::{self, TransferChecked},
+};
+use oapp::endpoint_cpi::LzAccount;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address,
+    instruction::{ExecuteInstruction, TransferHookInstruction},
+};
+use spl_type_length_value::state::TlvStateBorrowed;
+
+// The transfer hook accounts lead the remaining accounts of the instructions moving tokens:
+//      0 - transfer hook program
+//      1 - extra account metas of the mint
+//      2.. - extra accounts, in the order of the extra account metas
+
+/// Returns the transfer hook program of the mint, if any.
+pub fn get_program_id(token_mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    let token_mint_data = token_mint.try_borrow_data()?;
+    let token_mint_ext = StateWithExtensions::<MintState>::unpack(&token_mint_data)?;
+    Ok(transfer_hook::get_program_id(&token_mint_ext))
+}
+
+/// Returns the address of the extra account metas, or this program if the mint has no hook.
+pub fn get_extra_account_metas(token_mint: &AccountInfo) -> Result<Pubkey> {
+    Ok(match get_program_id(token_mint)? {
+        Some(program_id) => get_extra_account_metas_address(token_mint.key, &program_id),
+        None => ID,
+    })
+}
+
+/// Splits the transfer hook accounts off the front of the remaining accounts.
+pub fn split_accounts<'c, 'info>(
+    token_mint: &AccountInfo,
+    accounts: &'c [AccountInfo<'info>],
+) -> Result<(&'c [AccountInfo<'info>], &'c [AccountInfo<'info>])> {
+    let Some(program_id) = get_program_id(token_mint)? else {
+        return Ok((&[], accounts));
+    };
+    require!(accounts.len() >= 2, OFTError::InvalidTransferHook);
+    require_keys_eq!(accounts[0].key(), program_id, OFTError::InvalidTransferHook);
+    require_keys_eq!(
+        accounts[1].key(),
+        get_extra_account_metas_address(token_mint.key, &program_id),
+        OFTError::InvalidTransferHook
+    );
+    let extra_accounts_len = if accounts[1].data_is_empty() {
+        0
+    } else {
+        let data = accounts[1].try_borrow_data()?;
+        let state = TlvStateBorrowed::unpack(&data)?;
+        ExtraAccountMetaList::unpack_with_tlv_state::<ExecuteInstruction>(&state)?
+            .data()
+            .len()
+    };
+    require!(accounts.len() >= 2 + extra_accounts_len, OFTError::InvalidTransferHook);
+    Ok(accounts.split_at(2 + extra_accounts_len))
+}
+
+/// `token_interface::transfer_checked`, passing the transfer hook accounts along if any.
+pub fn transfer_checked<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+    hook_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    if hook_accounts.is_empty() {
+        return token_interface::transfer_checked(ctx, amount, decimals);
+    }
+    invoke_transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        hook_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Resolves the transfer hook accounts of a transfer for lz_receive_types. Only the data of the
+/// mint and the extra account metas is available, so metas seeded by other account data fail.
+/// Metas seeded by the amount only match if amount_ld is the amount transferred, see
+/// PeerConfig::get_ata_rent_fee_ld.
+pub fn get_lz_accounts(
+    token_mint: &AccountInfo,
+    extra_account_metas: &AccountInfo,
+    source: Pubkey,
+    destination: Pubkey,
+    authority: Pubkey,
+    amount_ld: u64,
+) -> Result<Vec<LzAccount>> {
+    let Some(program_id) = get_program_id(token_mint)? else {
+        return Ok(vec![]);
+    };
+    let extra_account_metas_address = get_extra_account_metas_address(token_mint.key, &program_id);
+    require_keys_eq!(
+        extra_account_metas.key(),
+        extra_account_metas_address,
+        OFTError::InvalidTransferHook
+    );
+    let mut accounts = vec![
+        LzAccount { pubkey: program_id, is_signer: false, is_writable: false },
+        LzAccount { pubkey: extra_account_metas_address, is_signer: false, is_writable: false },
+    ];
+    if extra_account_metas.data_is_empty() {
+        return Ok(accounts);
+    }
+
+    let token_mint_data = token_mint.try_borrow_data()?;
+    let data = extra_account_metas.try_borrow_data()?;
+    let state = TlvStateBorrowed::unpack(&data)?;
+    let extra_metas = ExtraAccountMetaList::unpack_with_tlv_state::<ExecuteInstruction>(&state)?;
+    let instruction_data = TransferHookInstruction::Execute { amount: amount_ld }.pack();
+    // the accounts of the execute instruction of the hook
+    let mut account_keys =
+        vec![source, *token_mint.key, destination, authority, extra_account_metas_address];
+    for extra_meta in extra_metas.data() {
+        let meta = extra_meta.resolve(&instruction_data, &program_id, |index| {
+            let data = match index {
+                1 => Some(&token_mint_data[..]),
+                4 => Some(&data[..]),
+                _ => None,
+            };
+            account_keys.get(index).map(|key| (key, data))
+        })?;
+        account_keys.push(meta.pubkey);
+        accounts.push(LzAccount {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        });
+    }
+    Ok(accounts)
+}
@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod test_account_version {
+    use anchor_lang::prelude::*;
+    use anchor_lang::{solana_program::hash::hash, Discriminator};
+    use oft::state::{OFTStore, OFTType, PeerConfig, RateLimiterType, ACCOUNT_VERSION};
+
+    // Accounts of the baseline layouts, encoded and allocated by the baseline program
+    const OFT_STORE_V0: &[u8] = include_bytes!("fixtures/oft_store_v0.bin");
+    const PEER_CONFIG_V0: &[u8] = include_bytes!("fixtures/peer_config_v0.bin");
+    const OFT_STORE_V0_LEN: usize = 8 + 215;
+    const PEER_CONFIG_V0_LEN: usize = 8 + 1646;
+
+    // the realloc and the rewrite of migrate_account
+    fn migrate<T: AccountSerialize>(account: &T, space: usize) -> Vec<u8> {
+        let mut data = vec![0u8; space];
+        account.try_serialize(&mut &mut data[..]).unwrap();
+        data
+    }
+
+    fn assert_oft_store_v0(oft_store: &OFTStore) {
+        assert!(oft_store.oft_type == OFTType::Adapter);
+        assert_eq!(oft_store.ld2sd_rate, 1000);
+        assert_eq!(oft_store.token_mint, Pubkey::new_from_array([1; 32]));
+        assert_eq!(oft_store.token_escrow, Pubkey::new_from_array([2; 32]));
+        assert_eq!(oft_store.endpoint_program, Pubkey::new_from_array([3; 32]));
+        assert_eq!(oft_store.bump, 254);
+        assert_eq!(oft_store.tvl_ld, 5_000_000);
+        assert_eq!(oft_store.admin, Pubkey::new_from_array([4; 32]));
+        assert_eq!(oft_store.default_fee_bps, 25);
+        assert!(!oft_store.paused);
+        assert_eq!(oft_store.pauser, Some(Pubkey::new_from_array([5; 32])));
+        assert_eq!(oft_store.unpauser, None);
+        // the fields added since are at their defaults
+        assert_eq!(oft_store.escrow_seed, oft_store.token_escrow);
+        assert_eq!(oft_store.total_fee_ld, 0);
+        assert_eq!(oft_store.max_referral_fee_bps, 0);
+        assert_eq!(oft_store.default_oft_limits.max_amount_ld, u64::MAX);
+        assert_eq!(oft_store.paused_until, None);
+        assert_eq!(oft_store.guardian, None);
+        assert_eq!(oft_store.timelock_delay, 0);
+        assert!(oft_store.outbound_rate_limiter.is_none());
+        assert!(!oft_store.native_sol);
+    }
+
+    fn assert_peer_config_v0(peer: &PeerConfig) {
+        assert_eq!(peer.peer_address, [7; 32]);
+        assert_eq!(peer.enforced_options.send, vec![0, 3, 1, 0, 17]);
+        assert!(peer.enforced_options.send_and_call.is_empty());
+        assert_eq!(peer.fee_bps, Some(30));
+        assert_eq!(peer.bump, 253);
+        let outbound = peer.outbound_rate_limiter.as_ref().unwrap();
+        assert_eq!(outbound.tokens, 400_000);
+        assert_eq!(outbound.last_refill_time, 1_690_000_000);
+        let inbound = peer.inbound_rate_limiter.as_ref().unwrap();
+        assert_eq!(inbound.capacity, 2_000_000);
+        assert_eq!(inbound.refill_per_second, 20);
+        assert!(matches!(inbound.limiter_type, RateLimiterType::TokenBucket));
+        // the fields added since are at their defaults
+        assert!(!peer.inbound_paused);
+        assert!(peer.inbound_circuit_breaker.is_none());
+        assert_eq!(peer.native_fee, None);
+        assert_eq!(peer.stats.sent_count, 0);
+        assert_eq!(peer.ata_rent_fee_ld, None);
+    }
+
+    #[test]
+    fn test_discriminator() {
+        assert_eq!(OFTStore::DISCRIMINATOR, hash(b"account:OFTStore").to_bytes()[..8]);
+        assert_eq!(PeerConfig::DISCRIMINATOR, hash(b"account:PeerConfig").to_bytes()[..8]);
+        assert_eq!(OFT_STORE_V0[..8], OFTStore::DISCRIMINATOR);
+        assert_eq!(PEER_CONFIG_V0[..8], PeerConfig::DISCRIMINATOR);
+    }
+
+    #[test]
+    fn test_layout_sizes() {
+        assert_eq!(OFT_STORE_V0.len(), OFT_STORE_V0_LEN);
+        assert_eq!(PEER_CONFIG_V0.len(), PEER_CONFIG_V0_LEN);
+        // the legacy layouts are recognized by their size
+        assert_ne!(8 + OFTStore::INIT_SPACE, OFT_STORE_V0_LEN);
+        assert_ne!(8 + PeerConfig::INIT_SPACE, PEER_CONFIG_V0_LEN);
+    }
+
+    #[test]
+    fn test_oft_store_v0() {
+        let oft_store = OFTStore::try_deserialize(&mut &OFT_STORE_V0[..]).unwrap();
+        assert_oft_store_v0(&oft_store);
+        assert_eq!(oft_store.version, 0);
+        // a legacy account can not be written until it is migrated
+        assert!(oft_store.try_serialize(&mut vec![]).is_err());
+    }
+
+    #[test]
+    fn test_peer_config_v0() {
+        let peer = PeerConfig::try_deserialize(&mut &PEER_CONFIG_V0[..]).unwrap();
+        assert_peer_config_v0(&peer);
+        assert_eq!(peer.version, 0);
+        assert!(peer.try_serialize(&mut vec![]).is_err());
+    }
+
+    #[test]
+    fn test_migrate_oft_store() {
+        let mut oft_store = OFTStore::try_deserialize(&mut &OFT_STORE_V0[..]).unwrap();
+        oft_store.version = ACCOUNT_VERSION;
+        let data = migrate(&oft_store, 8 + OFTStore::INIT_SPACE);
+
+        let oft_store = OFTStore::try_deserialize(&mut &data[..]).unwrap();
+        assert_oft_store_v0(&oft_store);
+        assert_eq!(oft_store.version, ACCOUNT_VERSION);
+        // the version leads the current layout
+        assert_eq!(data[8], ACCOUNT_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_peer_config() {
+        let mut peer = PeerConfig::try_deserialize(&mut &PEER_CONFIG_V0[..]).unwrap();
+        peer.version = ACCOUNT_VERSION;
+        let data = migrate(&peer, 8 + PeerConfig::INIT_SPACE);
+
+        let peer = PeerConfig::try_deserialize(&mut &data[..]).unwrap();
+        assert_peer_config_v0(&peer);
+        assert_eq!(peer.version, ACCOUNT_VERSION);
+    }
+
+    #[test]
+    fn test_unknown_version() {
+        let mut oft_store = OFTStore::try_deserialize(&mut &OFT_STORE_V0[..]).unwrap();
+        oft_store.version = ACCOUNT_VERSION;
+        let mut data = migrate(&oft_store, 8 + OFTStore::INIT_SPACE);
+        data[8] = ACCOUNT_VERSION + 1;
+        assert!(OFTStore::try_deserialize(&mut &data[..]).is_err());
+        // an account of the current size is only read at version 0 while being initialized
+        data[8] = 0;
+        assert!(OFTStore::try_deserialize(&mut &data[..]).is_err());
+        assert!(OFTStore::try_deserialize_unchecked(&mut &data[..]).is_ok());
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod test_circuit_breaker {
+    use oft::state::{CircuitBreaker, CIRCUIT_BREAKER_MAX_APPROVED_GUIDS};
+
+    const GUID: [u8; 32] = [1; 32];
+    const OTHER_GUID: [u8; 32] = [2; 32];
+    const THIRD_GUID: [u8; 32] = [3; 32];
+
+    fn circuit_breaker(volume: u64, last_update_time: u64) -> CircuitBreaker {
+        CircuitBreaker {
+            threshold: 1_000,
+            window: 100,
+            volume,
+            last_update_time,
+            approved_guids: vec![],
+        }
+    }
+
+    #[test]
+    fn test_trip() {
+        let mut breaker = circuit_breaker(0, 1_000);
+        assert!(breaker.try_record(600, &GUID, 1_000));
+        assert_eq!(breaker.volume, 600);
+        // up to the threshold is accepted
+        assert!(breaker.try_record(400, &GUID, 1_000));
+        assert_eq!(breaker.volume, 1_000);
+        // above it trips without recording the amount
+        assert!(!breaker.try_record(1, &GUID, 1_000));
+        assert_eq!(breaker.volume, 1_000);
+        assert!(!breaker.try_record(u64::MAX, &GUID, 1_000));
+        assert_eq!(breaker.volume, 1_000);
+    }
+
+    #[test]
+    fn test_decay() {
+        let mut breaker = circuit_breaker(1_000, 1_000);
+        // the threshold decays over one window: 10% after 10 seconds
+        assert!(breaker.try_record(100, &GUID, 1_010));
+        assert_eq!(breaker.volume, 1_000);
+        assert_eq!(breaker.last_update_time, 1_010);
+        // fully decayed after a window
+        assert!(breaker.try_record(0, &GUID, 1_500));
+        assert_eq!(breaker.volume, 0);
+        // a clock behind the last update does not decay or move the update time back
+        breaker.volume = 500;
+        assert!(breaker.try_record(0, &GUID, 1_400));
+        assert_eq!(breaker.volume, 500);
+        assert_eq!(breaker.last_update_time, 1_500);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut breaker = circuit_breaker(1_000, 1_000);
+        breaker.reset(2_000);
+        assert_eq!(breaker.volume, 0);
+        assert_eq!(breaker.last_update_time, 2_000);
+    }
+
+    #[test]
+    fn test_resume_delivers_approved_messages() {
+        let mut breaker = circuit_breaker(0, 1_000);
+        // two messages are parked while the breaker is tripped
+        assert!(breaker.try_record(900, &GUID, 1_000));
+        assert!(!breaker.try_record(800, &OTHER_GUID, 1_000));
+        assert!(!breaker.try_record(700, &THIRD_GUID, 1_000));
+        breaker.resume(vec![OTHER_GUID, THIRD_GUID], 1_010).unwrap();
+        assert_eq!(breaker.volume, 0);
+
+        // together they exceed the threshold, but both are delivered without being recorded
+        assert!(breaker.try_record(800, &OTHER_GUID, 1_010));
+        assert!(breaker.try_record(700, &THIRD_GUID, 1_010));
+        assert_eq!(breaker.volume, 0);
+        assert!(breaker.approved_guids.is_empty());
+        // each approval is used once
+        assert!(breaker.try_record(600, &OTHER_GUID, 1_010));
+        assert!(!breaker.try_record(700, &THIRD_GUID, 1_010));
+        assert_eq!(breaker.volume, 600);
+    }
+
+    #[test]
+    fn test_resume_replaces_approved_guids() {
+        let mut breaker = circuit_breaker(0, 1_000);
+        breaker.resume(vec![GUID], 1_000).unwrap();
+        breaker.resume(vec![OTHER_GUID], 1_000).unwrap();
+        assert!(!breaker.try_record(5_000, &GUID, 1_000));
+        assert!(breaker.try_record(5_000, &OTHER_GUID, 1_000));
+        // the approvals are bounded by the space of the account
+        assert!(breaker
+            .resume(vec![GUID; CIRCUIT_BREAKER_MAX_APPROVED_GUIDS + 1], 1_000)
+            .is_err());
+        assert!(breaker.resume(vec![GUID; CIRCUIT_BREAKER_MAX_APPROVED_GUIDS], 1_000).is_ok());
+    }
+}
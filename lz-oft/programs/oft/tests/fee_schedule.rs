@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod test_fee_schedule {
+    use anchor_lang::prelude::*;
+    use oft::instructions::calculate_oft_fee;
+    use oft::state::{FeeSchedule, FeeTier, OFTStore, PeerConfig};
+    use proptest::prelude::*;
+
+    fn oft_store(ld2sd_rate: u64, default_fee_bps: u16) -> OFTStore {
+        let mut oft_store =
+            OFTStore::deserialize(&mut &vec![0u8; OFTStore::INIT_SPACE][..]).unwrap();
+        oft_store.ld2sd_rate = ld2sd_rate;
+        oft_store.default_fee_bps = default_fee_bps;
+        oft_store
+    }
+
+    fn peer(fee_schedule: Option<FeeSchedule>) -> PeerConfig {
+        let mut peer =
+            PeerConfig::deserialize(&mut &vec![0u8; PeerConfig::INIT_SPACE][..]).unwrap();
+        peer.fee_schedule = fee_schedule;
+        peer
+    }
+
+    fn tiered_schedule() -> FeeSchedule {
+        FeeSchedule {
+            tiers: vec![
+                FeeTier { min_amount_ld: 0, fee_bps: 100 },
+                FeeTier { min_amount_ld: 10_000, fee_bps: 50 },
+                FeeTier { min_amount_ld: 1_000_000, fee_bps: 10 },
+            ],
+            min_fee_ld: 20,
+            max_fee_ld: 2_000,
+        }
+    }
+
+    #[test]
+    fn test_fee_schedule_tiers() {
+        let fee_schedule = tiered_schedule();
+        assert!(fee_schedule.is_valid());
+        // min fee applies to small amounts
+        assert_eq!(fee_schedule.calculate_fee(1_000), 20);
+        assert_eq!(fee_schedule.calculate_fee(9_999), 99);
+        assert_eq!(fee_schedule.calculate_fee(10_000), 50);
+        assert_eq!(fee_schedule.calculate_fee(99_999), 499);
+        assert_eq!(fee_schedule.calculate_fee(1_000_000), 1_000);
+        // max fee caps large amounts
+        assert_eq!(fee_schedule.calculate_fee(100_000_000), 2_000);
+        // the min fee never exceeds the amount
+        assert_eq!(fee_schedule.calculate_fee(10), 10);
+        assert_eq!(fee_schedule.calculate_fee(0), 0);
+    }
+
+    #[test]
+    fn test_invalid_fee_schedules() {
+        let mut unsorted = tiered_schedule();
+        unsorted.tiers.swap(0, 1);
+        assert!(!unsorted.is_valid());
+
+        let mut min_above_max = tiered_schedule();
+        min_above_max.min_fee_ld = 2_001;
+        assert!(!min_above_max.is_valid());
+
+        let mut fee_too_high = tiered_schedule();
+        fee_too_high.tiers[0].fee_bps = 10_000;
+        assert!(!fee_too_high.is_valid());
+    }
+
+    #[test]
+    fn test_fee_schedule_takes_precedence() {
+        let oft_store = oft_store(1, 1_000);
+        assert_eq!(calculate_oft_fee(10_000, &oft_store, &peer(None)), 1_000);
+        assert_eq!(calculate_oft_fee(10_000, &oft_store, &peer(Some(tiered_schedule()))), 50);
+    }
+
+    fn fee_schedule_strategy() -> impl Strategy<Value = FeeSchedule> {
+        (prop::collection::btree_map(any::<u64>(), 0..10_000u16, 0..=8), any::<u64>(), any::<u64>())
+            .prop_map(|(tiers, fee_a, fee_b)| FeeSchedule {
+                tiers: tiers
+                    .into_iter()
+                    .map(|(min_amount_ld, fee_bps)| FeeTier { min_amount_ld, fee_bps })
+                    .collect(),
+                min_fee_ld: fee_a.min(fee_b),
+                max_fee_ld: fee_a.max(fee_b),
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_fee_never_exceeds_amount(
+            fee_schedule in fee_schedule_strategy(),
+            amount_ld in any::<u64>(),
+        ) {
+            prop_assert!(fee_schedule.is_valid());
+            let fee = fee_schedule.calculate_fee(amount_ld);
+            prop_assert!(fee <= amount_ld);
+            prop_assert!(fee <= fee_schedule.max_fee_ld);
+            if amount_ld >= fee_schedule.min_fee_ld {
+                prop_assert!(fee >= fee_schedule.min_fee_ld);
+            }
+        }
+
+        #[test]
+        fn prop_oft_fee_is_dust_aligned(
+            fee_schedule in prop::option::of(fee_schedule_strategy()),
+            decimals_diff in 0..=12u32,
+            default_fee_bps in 0..10_000u16,
+            amount_ld in any::<u64>(),
+        ) {
+            let oft_store = oft_store(10u64.pow(decimals_diff), default_fee_bps);
+            let amount_ld = oft_store.remove_dust(amount_ld);
+            let fee = calculate_oft_fee(amount_ld, &oft_store, &peer(fee_schedule));
+            prop_assert!(fee <= amount_ld);
+            prop_assert_eq!(fee % oft_store.ld2sd_rate, 0);
+            prop_assert_eq!((amount_ld - fee) % oft_store.ld2sd_rate, 0);
+        }
+    }
+}
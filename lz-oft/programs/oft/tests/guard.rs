@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod test_guard {
+    use oft::instructions::RateLimitParams;
+    use oft::state::{RateLimiter, RateLimiterType};
+
+    fn rate_limiter(limiter_type: RateLimiterType) -> Option<RateLimiter> {
+        Some(RateLimiter {
+            capacity: 1_000,
+            tokens: 600,
+            refill_per_second: 10,
+            last_refill_time: 1_000,
+            limiter_type,
+        })
+    }
+
+    fn params(
+        capacity: Option<u64>,
+        refill_per_second: Option<u64>,
+        limiter_type: Option<RateLimiterType>,
+    ) -> RateLimitParams {
+        RateLimitParams { capacity, refill_per_second, limiter_type }
+    }
+
+    fn window(window: u64) -> RateLimiterType {
+        RateLimiterType::Window { window }
+    }
+
+    #[test]
+    fn test_tightens_capacity_and_rate() {
+        let limiter = rate_limiter(RateLimiterType::TokenBucket);
+        assert!(params(Some(999), None, None).tightens(&limiter));
+        assert!(!params(Some(1_000), None, None).tightens(&limiter));
+        assert!(params(None, Some(9), None).tightens(&limiter));
+        // the guardian can not keep or raise the refill rate
+        assert!(!params(None, Some(10), None).tightens(&limiter));
+        assert!(!params(None, Some(11), None).tightens(&limiter));
+        // every provided value has to be lower
+        assert!(params(Some(999), Some(9), None).tightens(&limiter));
+        assert!(!params(Some(999), Some(11), None).tightens(&limiter));
+        assert!(!params(Some(1_001), Some(9), None).tightens(&limiter));
+        // nothing to tighten
+        assert!(!params(None, None, None).tightens(&limiter));
+    }
+
+    #[test]
+    fn test_tightens_limiter_type() {
+        let bucket = rate_limiter(RateLimiterType::TokenBucket);
+        let windowed = rate_limiter(window(60));
+        // the guardian can not switch types, in either direction
+        assert!(!params(None, None, Some(window(3_600))).tightens(&bucket));
+        assert!(!params(None, None, Some(RateLimiterType::TokenBucket)).tightens(&windowed));
+        assert!(!params(Some(1), None, Some(RateLimiterType::TokenBucket)).tightens(&bucket));
+        // a longer window decays slower
+        assert!(params(None, None, Some(window(61))).tightens(&windowed));
+        assert!(!params(None, None, Some(window(60))).tightens(&windowed));
+        assert!(!params(None, None, Some(window(59))).tightens(&windowed));
+        assert!(!params(Some(999), None, Some(window(59))).tightens(&windowed));
+    }
+
+    #[test]
+    fn test_tightens_without_limiter() {
+        // any limit is more restrictive than none
+        assert!(params(Some(u64::MAX), None, None).tightens(&None));
+        assert!(params(None, Some(u64::MAX), None).tightens(&None));
+        assert!(params(None, None, Some(window(1))).tightens(&None));
+        assert!(!params(None, None, None).tightens(&None));
+    }
+
+    #[test]
+    fn test_loosens() {
+        let limiter = rate_limiter(RateLimiterType::TokenBucket);
+        assert!(RateLimitParams::loosens(&None, &limiter));
+        assert!(!RateLimitParams::loosens(&None, &None));
+        assert!(!RateLimitParams::loosens(&Some(params(Some(u64::MAX), None, None)), &None));
+        // set_capacity refills the bucket, so only a capacity above the tokens left loosens it
+        assert!(RateLimitParams::loosens(&Some(params(Some(601), None, None)), &limiter));
+        assert!(!RateLimitParams::loosens(&Some(params(Some(600), None, None)), &limiter));
+        assert!(RateLimitParams::loosens(&Some(params(None, Some(11), None)), &limiter));
+        assert!(!RateLimitParams::loosens(&Some(params(None, Some(10), None)), &limiter));
+        assert!(RateLimitParams::loosens(&Some(params(None, None, Some(window(60)))), &limiter));
+        assert!(!RateLimitParams::loosens(
+            &Some(params(None, None, Some(RateLimiterType::TokenBucket))),
+            &limiter
+        ));
+        let windowed = rate_limiter(window(60));
+        assert!(RateLimitParams::loosens(&Some(params(None, None, Some(window(59)))), &windowed));
+        assert!(!RateLimitParams::loosens(&Some(params(None, None, Some(window(61)))), &windowed));
+        assert!(!RateLimitParams::loosens(&Some(params(None, None, None)), &windowed));
+    }
+}
@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod test_msg_codec {
-    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::prelude::*;
     use oft::compose_msg_codec;
     use oft::msg_codec;
+    use oft::state::PeerConfig;
 
     #[test]
     fn test_msg_codec_with_compose_msg() {
@@ -33,6 +34,46 @@ mod test_msg_codec {
         assert_eq!(msg_codec::compose_msg(&encoded), None);
     }
 
+    #[test]
+    fn test_msg_codec_to_token_account() {
+        let send_to: [u8; 32] = [1; 32];
+        let amount_sd: u64 = 123456789;
+        let encoded = msg_codec::encode_to_token_account(send_to, amount_sd);
+        assert_eq!(encoded.len(), 41);
+        assert_eq!(msg_codec::send_to(&encoded), send_to);
+        assert_eq!(msg_codec::amount_sd(&encoded), amount_sd);
+        assert_eq!(msg_codec::compose_msg(&encoded), None);
+        assert!(msg_codec::is_to_token_account(&encoded));
+
+        // regular and composed messages are never flagged
+        let sender: Pubkey = Pubkey::new_unique();
+        assert!(!msg_codec::is_to_token_account(&msg_codec::encode(
+            send_to, amount_sd, sender, &None
+        )));
+        assert!(!msg_codec::is_to_token_account(&msg_codec::encode(
+            send_to,
+            amount_sd,
+            sender,
+            &Some(vec![1])
+        )));
+    }
+
+    #[test]
+    fn test_msg_codec_to_token_account_non_solana_eid() {
+        let mut peer =
+            PeerConfig::deserialize(&mut &vec![0u8; PeerConfig::INIT_SPACE][..]).unwrap();
+        // the peer on eid 30101 is an EVM OFT, which reads the flag byte as a compose msg
+        let encoded = msg_codec::encode_to_token_account([1; 32], 123456789);
+        assert_eq!(encoded.len(), 41);
+        assert!(!peer.accepts_to_token_account(&None));
+
+        // a peer that decodes the flag accepts it whatever its eid, but never with a compose msg
+        peer.token_account_dest = true;
+        assert!(peer.accepts_to_token_account(&None));
+        assert!(!peer.accepts_to_token_account(&Some(vec![1])));
+        assert!(msg_codec::is_to_token_account(&encoded));
+    }
+
     #[test]
     fn test_compose_msg_codec() {
         let nonce: u64 = 123456789;
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod test_native_fee {
+    use anchor_lang::prelude::*;
+    use oft::instructions::{calculate_native_fee, fee_vault_shortfall};
+    use oft::state::PeerConfig;
+
+    fn peer(native_fee: u64) -> PeerConfig {
+        let mut peer =
+            PeerConfig::deserialize(&mut &vec![0u8; PeerConfig::INIT_SPACE][..]).unwrap();
+        peer.native_fee = Some(native_fee);
+        peer
+    }
+
+    #[test]
+    fn test_send_small_native_fee() {
+        let rent = Rent::default();
+        let oft_native_fee = calculate_native_fee(&peer(5_000), false);
+        assert_eq!(oft_native_fee, 5_000);
+        assert!(oft_native_fee < rent.minimum_balance(0));
+
+        // the deposit alone would leave an empty vault below the rent-exempt minimum
+        assert!(fee_vault_shortfall(oft_native_fee, &rent) > 0);
+        // init_oft and init_fee_vault fund the vault with what it lacks
+        let funded_lamports = fee_vault_shortfall(0, &rent);
+        assert_eq!(funded_lamports, rent.minimum_balance(0));
+        assert_eq!(fee_vault_shortfall(funded_lamports, &rent), 0);
+        // after which any deposit keeps it rent exempt
+        assert_eq!(fee_vault_shortfall(funded_lamports + oft_native_fee, &rent), 0);
+        assert_eq!(fee_vault_shortfall(funded_lamports + 1, &rent), 0);
+    }
+
+    #[test]
+    fn test_fee_exempt_native_fee() {
+        assert_eq!(calculate_native_fee(&peer(5_000), true), 0);
+        let mut peer = peer(0);
+        peer.native_fee = None;
+        assert_eq!(calculate_native_fee(&peer, false), 0);
+    }
+}
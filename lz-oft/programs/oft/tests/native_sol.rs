@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod test_native_sol {
+    use anchor_lang::prelude::*;
+    use oft::instructions::can_receive_lamports;
+
+    #[test]
+    fn test_can_receive_lamports() {
+        let rent = Rent::default();
+        let rent_exempt_lamports = rent.minimum_balance(0);
+
+        // a new wallet is only created with the rent-exempt minimum, smaller amounts go to wSOL
+        assert!(!can_receive_lamports(0, 0, 1, &rent));
+        assert!(!can_receive_lamports(0, 0, rent_exempt_lamports - 1, &rent));
+        assert!(can_receive_lamports(0, 0, rent_exempt_lamports, &rent));
+
+        // an existing wallet takes any amount
+        assert!(can_receive_lamports(rent_exempt_lamports, 0, 1, &rent));
+        assert!(can_receive_lamports(rent_exempt_lamports - 1, 0, 1, &rent));
+
+        // an account with data needs more
+        assert!(!can_receive_lamports(0, 165, rent_exempt_lamports, &rent));
+        assert!(can_receive_lamports(0, 165, rent.minimum_balance(165), &rent));
+    }
+}
@@ -0,0 +1,345 @@
+#[cfg(test)]
+mod test_rate_limiter {
+    use anchor_lang::prelude::*;
+    use oft::state::{PeerConfig, RateLimiter, RateLimiterType, ACCOUNT_VERSION};
+    use proptest::prelude::*;
+
+    // A PeerConfig encoded by the baseline program, with limiters of the 32 bytes layout
+    const PEER_CONFIG_V0: &[u8] = include_bytes!("fixtures/peer_config_v0.bin");
+
+    fn rate_limiter(tokens: u64, last_refill_time: u64) -> RateLimiter {
+        RateLimiter {
+            capacity: 1_000,
+            tokens,
+            refill_per_second: 10,
+            last_refill_time,
+            limiter_type: RateLimiterType::TokenBucket,
+        }
+    }
+
+    fn window_limiter(tokens: u64, last_refill_time: u64) -> RateLimiter {
+        RateLimiter {
+            limiter_type: RateLimiterType::Window { window: 60 },
+            ..rate_limiter(tokens, last_refill_time)
+        }
+    }
+
+    #[test]
+    fn test_projected_tokens() {
+        let limiter = rate_limiter(100, 1_000);
+        assert_eq!(limiter.projected_tokens(1_000), 100);
+        assert_eq!(limiter.projected_tokens(1_010), 200);
+        // capped at the capacity
+        assert_eq!(limiter.projected_tokens(2_000), 1_000);
+        // a clock behind the last refill does not add tokens
+        assert_eq!(limiter.projected_tokens(900), 100);
+        // the projection does not mutate the limiter
+        assert_eq!(limiter.tokens, 100);
+    }
+
+    #[test]
+    fn test_seconds_to_full() {
+        let limiter = rate_limiter(100, 1_000);
+        assert_eq!(limiter.seconds_to_full(1_000), Some(90));
+        assert_eq!(limiter.seconds_to_full(1_010), Some(80));
+        assert_eq!(limiter.seconds_to_full(1_090), Some(0));
+        // rounds up partial seconds
+        assert_eq!(rate_limiter(995, 1_000).seconds_to_full(1_000), Some(1));
+        // never refills
+        let limiter = RateLimiter { refill_per_second: 0, ..rate_limiter(100, 1_000) };
+        assert_eq!(limiter.seconds_to_full(1_000), None);
+        let limiter = RateLimiter { refill_per_second: 0, ..rate_limiter(1_000, 1_000) };
+        assert_eq!(limiter.seconds_to_full(1_000), Some(0));
+    }
+
+    #[test]
+    fn test_window_decay() {
+        // 400 in flight decays by 1_000 every 60 seconds, regardless of refill_per_second
+        let limiter = window_limiter(600, 1_000);
+        assert_eq!(limiter.projected_tokens(1_000), 600);
+        assert_eq!(limiter.projected_tokens(1_006), 700);
+        assert_eq!(limiter.projected_tokens(1_024), 1_000);
+        assert_eq!(limiter.projected_tokens(1_100), 1_000);
+        // 400 * 60 / 1_000 = 24 seconds
+        assert_eq!(limiter.seconds_to_full(1_000), Some(24));
+        assert_eq!(limiter.seconds_to_full(1_023), Some(1));
+        assert_eq!(limiter.seconds_to_full(1_024), Some(0));
+    }
+
+    #[test]
+    fn test_limiter_type_loosens() {
+        let bucket = RateLimiterType::TokenBucket;
+        let short = RateLimiterType::Window { window: 60 };
+        let long = RateLimiterType::Window { window: 3_600 };
+        assert!(!bucket.loosens(&bucket));
+        assert!(bucket.loosens(&short));
+        assert!(short.loosens(&bucket));
+        assert!(short.loosens(&long));
+        assert!(!long.loosens(&short));
+        assert!(!RateLimiterType::Window { window: 0 }.is_valid());
+    }
+
+    #[test]
+    fn test_refill() {
+        let mut limiter = rate_limiter(100, 1_000);
+        limiter.refill(0, 1_010);
+        assert_eq!((limiter.tokens, limiter.last_refill_time), (200, 1_010));
+        // extra tokens are added on top of the accrued ones
+        limiter.refill(50, 1_011);
+        assert_eq!((limiter.tokens, limiter.last_refill_time), (260, 1_011));
+        // capped at the capacity
+        limiter.refill(10_000, 1_012);
+        assert_eq!(limiter.tokens, 1_000);
+    }
+
+    #[test]
+    fn test_refill_saturates() {
+        let mut limiter = RateLimiter {
+            capacity: u64::MAX,
+            tokens: u64::MAX - 1,
+            refill_per_second: u64::MAX,
+            ..rate_limiter(0, 0)
+        };
+        limiter.refill(u64::MAX, u64::MAX);
+        assert_eq!(limiter.tokens, u64::MAX);
+
+        let mut limiter = RateLimiter {
+            capacity: u64::MAX,
+            limiter_type: RateLimiterType::Window { window: 1 },
+            ..rate_limiter(0, 0)
+        };
+        limiter.refill(0, u64::MAX);
+        assert_eq!(limiter.tokens, u64::MAX);
+    }
+
+    #[test]
+    fn test_refill_with_clock_behind() {
+        let mut limiter = rate_limiter(100, 1_000);
+        limiter.refill(50, 900);
+        // extra tokens still count, but the last refill time does not move back
+        assert_eq!((limiter.tokens, limiter.last_refill_time), (150, 1_000));
+        limiter.refill(0, 1_010);
+        assert_eq!(limiter.tokens, 250);
+    }
+
+    #[test]
+    fn test_set_capacity() {
+        let mut limiter = rate_limiter(100, 1_000);
+        limiter.set_capacity(500, 2_000);
+        assert_eq!((limiter.capacity, limiter.tokens, limiter.last_refill_time), (500, 500, 2_000));
+        // lowering the capacity also resets the bucket to full
+        limiter.try_consume(400, 2_000).unwrap();
+        limiter.set_capacity(200, 2_000);
+        assert_eq!(limiter.tokens, 200);
+    }
+
+    #[test]
+    fn test_set_rate() {
+        let mut limiter = rate_limiter(100, 1_000);
+        // tokens accrued at the old rate are kept
+        limiter.set_rate(100, 1_010);
+        assert_eq!((limiter.tokens, limiter.last_refill_time), (200, 1_010));
+        limiter.refill(0, 1_011);
+        assert_eq!(limiter.tokens, 300);
+        // a zero rate stops refilling
+        limiter.set_rate(0, 1_011);
+        limiter.refill(0, 5_000);
+        assert_eq!(limiter.tokens, 300);
+    }
+
+    #[test]
+    fn test_set_limiter_type() {
+        let mut limiter = rate_limiter(600, 1_000);
+        limiter.set_limiter_type(RateLimiterType::Window { window: 60 }, 1_010).unwrap();
+        // the amount in flight carries over
+        assert_eq!((limiter.tokens, limiter.last_refill_time), (700, 1_010));
+        limiter.refill(0, 1_016);
+        assert_eq!(limiter.tokens, 800);
+        assert!(limiter.set_limiter_type(RateLimiterType::Window { window: 0 }, 1_016).is_err());
+        assert_eq!(limiter.limiter_type, RateLimiterType::Window { window: 60 });
+    }
+
+    #[test]
+    fn test_try_consume() {
+        let mut limiter = rate_limiter(100, 1_000);
+        limiter.try_consume(200, 1_010).unwrap();
+        assert_eq!(limiter.tokens, 0);
+        // a failed consume leaves the limiter refilled
+        assert!(limiter.try_consume(101, 1_020).is_err());
+        assert_eq!((limiter.tokens, limiter.last_refill_time), (100, 1_020));
+        limiter.try_consume(0, 1_020).unwrap();
+        assert_eq!(limiter.tokens, 100);
+    }
+
+    fn limiter_type_strategy() -> impl Strategy<Value = RateLimiterType> {
+        prop_oneof![
+            Just(RateLimiterType::TokenBucket),
+            (1..1_000_000u64).prop_map(|window| RateLimiterType::Window { window }),
+        ]
+    }
+
+    fn rate_limiter_strategy() -> impl Strategy<Value = RateLimiter> {
+        (0..1_000_000_000_000_000u64, 0..1_000_000_000_000u64, 0..10_000_000_000u64).prop_flat_map(
+            |(capacity, refill_per_second, last_refill_time)| {
+                (0..=capacity, limiter_type_strategy()).prop_map(move |(tokens, limiter_type)| {
+                    RateLimiter {
+                        capacity,
+                        tokens,
+                        refill_per_second,
+                        last_refill_time,
+                        limiter_type,
+                    }
+                })
+            },
+        )
+    }
+
+    #[derive(Clone, Debug)]
+    enum Op {
+        Refill(u64),
+        TryConsume(u64),
+        SetRate(u64),
+        SetCapacity(u64),
+    }
+
+    fn op_strategy() -> impl Strategy<Value = (Op, u64)> {
+        (
+            prop_oneof![
+                any::<u64>().prop_map(Op::Refill),
+                any::<u64>().prop_map(Op::TryConsume),
+                any::<u64>().prop_map(Op::SetRate),
+                any::<u64>().prop_map(Op::SetCapacity),
+            ],
+            0..1_000_000u64,
+        )
+    }
+
+    #[test]
+    fn test_baseline_limiters() {
+        let peer = PeerConfig::try_deserialize(&mut &PEER_CONFIG_V0[..]).unwrap();
+        let mut outbound = peer.outbound_rate_limiter.clone().unwrap();
+        assert!(matches!(outbound.limiter_type, RateLimiterType::TokenBucket));
+        assert_eq!(outbound.capacity, 1_000_000);
+        assert_eq!(outbound.tokens, 400_000);
+        assert_eq!(outbound.refill_per_second, 10);
+        assert_eq!(outbound.last_refill_time, 1_690_000_000);
+        let inbound = peer.inbound_rate_limiter.clone().unwrap();
+        assert!(matches!(inbound.limiter_type, RateLimiterType::TokenBucket));
+        assert_eq!(inbound.capacity, 2_000_000);
+        assert_eq!(inbound.tokens, 2_000_000);
+        assert_eq!(inbound.refill_per_second, 20);
+        assert_eq!(inbound.last_refill_time, 1_690_000_100);
+
+        // they keep refilling as token buckets
+        assert_eq!(outbound.projected_tokens(1_690_000_100), 401_000);
+        outbound.try_consume(401_000, 1_690_000_100).unwrap();
+        assert_eq!(outbound.tokens, 0);
+
+        // and are rewritten in the current layout by the migration
+        let mut migrated = peer.clone();
+        migrated.version = ACCOUNT_VERSION;
+        let mut data = vec![0u8; 8 + PeerConfig::INIT_SPACE];
+        migrated.try_serialize(&mut &mut data[..]).unwrap();
+        let migrated = PeerConfig::try_deserialize(&mut &data[..]).unwrap();
+        let limiter = migrated.outbound_rate_limiter.unwrap();
+        assert!(matches!(limiter.limiter_type, RateLimiterType::TokenBucket));
+        assert_eq!(limiter.tokens, 400_000);
+        assert_eq!(limiter.last_refill_time, 1_690_000_000);
+        let limiter = migrated.inbound_rate_limiter.unwrap();
+        assert_eq!(limiter.capacity, 2_000_000);
+        assert_eq!(limiter.refill_per_second, 20);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_tokens_never_exceed_capacity(
+            mut limiter in rate_limiter_strategy(),
+            ops in prop::collection::vec(op_strategy(), 1..32),
+        ) {
+            let mut current_time = limiter.last_refill_time;
+            for (op, elapsed) in ops {
+                current_time += elapsed;
+                let last_refill_time = limiter.last_refill_time;
+                match op {
+                    Op::Refill(extra_tokens) => limiter.refill(extra_tokens, current_time),
+                    Op::TryConsume(amount) => {
+                        let available = limiter.projected_tokens(current_time);
+                        let result = limiter.try_consume(amount, current_time);
+                        prop_assert_eq!(result.is_ok(), amount <= available);
+                        if result.is_ok() {
+                            prop_assert_eq!(limiter.tokens, available - amount);
+                        }
+                    },
+                    Op::SetRate(refill_per_second) => limiter.set_rate(refill_per_second, current_time),
+                    Op::SetCapacity(capacity) => limiter.set_capacity(capacity, current_time),
+                }
+                prop_assert!(limiter.tokens <= limiter.capacity);
+                prop_assert!(limiter.last_refill_time >= last_refill_time);
+            }
+        }
+
+        #[test]
+        fn prop_projection_is_monotonic(
+            limiter in rate_limiter_strategy(),
+            elapsed in 0..1_000_000u64,
+            more_elapsed in 0..1_000_000u64,
+        ) {
+            let current_time = limiter.last_refill_time + elapsed;
+            let projected = limiter.projected_tokens(current_time);
+            prop_assert!(projected >= limiter.tokens);
+            prop_assert!(projected <= limiter.capacity);
+            prop_assert!(limiter.projected_tokens(current_time + more_elapsed) >= projected);
+        }
+
+        #[test]
+        fn prop_refill_matches_projection(
+            mut limiter in rate_limiter_strategy(),
+            elapsed in 0..1_000_000u64,
+        ) {
+            let current_time = limiter.last_refill_time + elapsed;
+            let projected = limiter.projected_tokens(current_time);
+            limiter.refill(0, current_time);
+            prop_assert_eq!(limiter.tokens, projected);
+            prop_assert_eq!(limiter.last_refill_time, current_time);
+        }
+
+        #[test]
+        fn prop_intermediate_refills_never_add_tokens(
+            limiter in rate_limiter_strategy(),
+            elapsed in 0..1_000_000u64,
+            more_elapsed in 0..1_000_000u64,
+        ) {
+            let current_time = limiter.last_refill_time + elapsed;
+            let mut stepped = limiter.clone();
+            stepped.refill(0, current_time);
+            stepped.refill(0, current_time + more_elapsed);
+            let direct = limiter.projected_tokens(current_time + more_elapsed);
+            if limiter.limiter_type == RateLimiterType::TokenBucket {
+                prop_assert_eq!(stepped.tokens, direct);
+            } else {
+                // the window decay rounds down on every refill
+                prop_assert!(stepped.tokens <= direct);
+            }
+        }
+
+        #[test]
+        fn prop_seconds_to_full_is_exact(
+            limiter in rate_limiter_strategy(),
+            elapsed in 0..1_000_000u64,
+        ) {
+            let current_time = limiter.last_refill_time + elapsed;
+            match limiter.seconds_to_full(current_time) {
+                Some(seconds) => {
+                    prop_assert_eq!(limiter.projected_tokens(current_time + seconds), limiter.capacity);
+                    if seconds > 0 {
+                        prop_assert!(limiter.projected_tokens(current_time + seconds - 1) < limiter.capacity);
+                    }
+                },
+                None => {
+                    prop_assert_eq!(limiter.refill_per_second, 0);
+                    prop_assert!(limiter.projected_tokens(current_time + 1_000_000) < limiter.capacity);
+                },
+            }
+        }
+    }
+}
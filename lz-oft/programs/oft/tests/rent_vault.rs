@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod test_rent_vault {
+    use anchor_lang::prelude::*;
+    use anchor_spl::token_2022::spl_token_2022::{
+        self,
+        solana_program::{program_option::COption, program_pack::Pack},
+        state::Mint as MintState,
+    };
+    use oft::instructions::{get_ata_len, rent_vault_spare_lamports};
+
+    fn mint_data() -> Vec<u8> {
+        let mut data = vec![0u8; MintState::LEN];
+        let mint = MintState {
+            mint_authority: COption::None,
+            supply: 0,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        MintState::pack(mint, &mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_rent_vault_spare_lamports() {
+        let rent = Rent::default();
+        let rent_exempt_lamports = rent.minimum_balance(0);
+        assert_eq!(rent_vault_spare_lamports(0, &rent), 0);
+        assert_eq!(rent_vault_spare_lamports(rent_exempt_lamports, &rent), 0);
+        // only what exceeds the rent-exempt minimum reimburses the rent of an ATA
+        let ata_rent = rent.minimum_balance(165);
+        assert!(rent_vault_spare_lamports(rent_exempt_lamports + ata_rent - 1, &rent) < ata_rent);
+        assert_eq!(rent_vault_spare_lamports(rent_exempt_lamports + ata_rent, &rent), ata_rent);
+    }
+
+    #[test]
+    fn test_get_ata_len() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = mint_data();
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &anchor_spl::token::ID,
+            false,
+            0,
+        );
+        assert_eq!(get_ata_len(&info).unwrap(), 165);
+
+        let mut lamports = 0;
+        let mut data = mint_data();
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+        // the account type and the ImmutableOwner extension
+        assert_eq!(get_ata_len(&info).unwrap(), 170);
+    }
+}
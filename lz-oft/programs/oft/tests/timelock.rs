@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod test_timelock {
+    use anchor_lang::prelude::*;
+    use oft::instructions::{
+        CircuitBreakerParams, OFTLimits, PeerConfigParam, RateLimitParams, SetOFTConfig,
+        SetOFTConfigParams,
+    };
+    use oft::state::{
+        CircuitBreaker, FeeSchedule, OFTStore, PeerConfig, RateLimiter, RateLimiterType,
+        SenderRateLimitConfig, ACCOUNT_VERSION, MAX_TIMELOCK_DELAY,
+    };
+
+    fn oft_store() -> OFTStore {
+        let mut oft_store =
+            OFTStore::deserialize(&mut &vec![0u8; OFTStore::INIT_SPACE][..]).unwrap();
+        oft_store.default_fee_bps = 100;
+        oft_store.max_referral_fee_bps = 1_000;
+        oft_store.default_oft_limits = OFTLimits { min_amount_ld: 10, max_amount_ld: 1_000 };
+        oft_store.timelock_delay = 3_600;
+        oft_store.outbound_rate_limiter = Some(rate_limiter());
+        oft_store
+    }
+
+    fn peer() -> PeerConfig {
+        let mut peer =
+            PeerConfig::deserialize(&mut &vec![0u8; PeerConfig::INIT_SPACE][..]).unwrap();
+        peer.fee_bps = Some(50);
+        peer.native_fee = Some(5_000);
+        peer.ata_rent_fee_ld = Some(2_000);
+        peer.oft_limits = Some(OFTLimits { min_amount_ld: 100, max_amount_ld: 500 });
+        peer.inbound_rate_limiter = Some(rate_limiter());
+        peer.sender_rate_limit =
+            Some(SenderRateLimitConfig { capacity: 100, refill_per_second: 1 });
+        peer.inbound_circuit_breaker =
+            Some(CircuitBreaker { threshold: 1_000, window: 60, ..Default::default() });
+        peer
+    }
+
+    fn rate_limiter() -> RateLimiter {
+        RateLimiter {
+            capacity: 1_000,
+            tokens: 600,
+            refill_per_second: 10,
+            last_refill_time: 1_000,
+            limiter_type: RateLimiterType::TokenBucket,
+        }
+    }
+
+    fn rate_limit(capacity: Option<u64>, refill_per_second: Option<u64>) -> RateLimitParams {
+        RateLimitParams { capacity, refill_per_second, limiter_type: None }
+    }
+
+    #[test]
+    fn test_oft_config_requires_timelock() {
+        let oft_store = oft_store();
+        let requires = |params: SetOFTConfigParams| params.requires_timelock(&oft_store);
+        let key = Pubkey::new_unique();
+
+        // roles and the native SOL mode always go through the timelock
+        assert!(requires(SetOFTConfigParams::Admin(key)));
+        assert!(requires(SetOFTConfigParams::Delegate(key)));
+        assert!(requires(SetOFTConfigParams::Pauser(None)));
+        assert!(requires(SetOFTConfigParams::Unpauser(Some(key))));
+        assert!(requires(SetOFTConfigParams::Guardian(Some(key))));
+        assert!(requires(SetOFTConfigParams::NativeSol(false)));
+
+        assert!(requires(SetOFTConfigParams::DefaultFee(101)));
+        assert!(!requires(SetOFTConfigParams::DefaultFee(100)));
+        assert!(!requires(SetOFTConfigParams::DefaultFee(0)));
+
+        assert!(requires(SetOFTConfigParams::MaxReferralFee(1_001)));
+        assert!(!requires(SetOFTConfigParams::MaxReferralFee(1_000)));
+
+        let limits = |min_amount_ld, max_amount_ld| {
+            SetOFTConfigParams::DefaultOFTLimits(OFTLimits { min_amount_ld, max_amount_ld })
+        };
+        assert!(requires(limits(10, 1_001)));
+        assert!(requires(limits(9, 1_000)));
+        assert!(!requires(limits(10, 1_000)));
+        assert!(!requires(limits(20, 500)));
+
+        assert!(requires(SetOFTConfigParams::Paused(false)));
+        assert!(!requires(SetOFTConfigParams::Paused(true)));
+
+        assert!(requires(SetOFTConfigParams::TimelockDelay(3_599)));
+        assert!(requires(SetOFTConfigParams::TimelockDelay(0)));
+        assert!(!requires(SetOFTConfigParams::TimelockDelay(3_600)));
+
+        // removing a limiter loosens it, adding one does not
+        assert!(requires(SetOFTConfigParams::OutboundRateLimit(None)));
+        assert!(!requires(SetOFTConfigParams::InboundRateLimit(None)));
+        assert!(!requires(SetOFTConfigParams::InboundRateLimit(Some(rate_limit(
+            Some(u64::MAX),
+            None
+        )))));
+        assert!(requires(SetOFTConfigParams::OutboundRateLimit(Some(rate_limit(None, Some(11))))));
+        assert!(!requires(SetOFTConfigParams::OutboundRateLimit(Some(rate_limit(None, Some(10))))));
+    }
+
+    #[test]
+    fn test_peer_config_requires_timelock() {
+        let oft_store = oft_store();
+        let peer = peer();
+        let requires = |param: PeerConfigParam| param.requires_timelock(&peer, &oft_store);
+
+        assert!(requires(PeerConfigParam::PeerAddress([1; 32])));
+
+        assert!(requires(PeerConfigParam::FeeBps(Some(51))));
+        assert!(!requires(PeerConfigParam::FeeBps(Some(50))));
+        // None falls back to the higher default fee
+        assert!(requires(PeerConfigParam::FeeBps(None)));
+
+        assert!(requires(PeerConfigParam::FeeSchedule(None)));
+        assert!(requires(PeerConfigParam::FeeSchedule(Some(FeeSchedule::default()))));
+
+        assert!(requires(PeerConfigParam::NativeFee(Some(5_001))));
+        assert!(!requires(PeerConfigParam::NativeFee(Some(5_000))));
+        // switching to the token fee adds a fee
+        assert!(requires(PeerConfigParam::NativeFee(None)));
+
+        assert!(requires(PeerConfigParam::AtaRentFee(Some(2_001))));
+        assert!(!requires(PeerConfigParam::AtaRentFee(Some(2_000))));
+        assert!(!requires(PeerConfigParam::AtaRentFee(None)));
+
+        // only peers that decode the flag may receive messages to token accounts
+        assert!(requires(PeerConfigParam::TokenAccountDest(true)));
+        assert!(!requires(PeerConfigParam::TokenAccountDest(false)));
+
+        let limits = |min_amount_ld, max_amount_ld| {
+            PeerConfigParam::OFTLimits(Some(OFTLimits { min_amount_ld, max_amount_ld }))
+        };
+        assert!(requires(limits(100, 501)));
+        assert!(requires(limits(99, 500)));
+        assert!(!requires(limits(100, 500)));
+        // None falls back to the looser default limits
+        assert!(requires(PeerConfigParam::OFTLimits(None)));
+
+        assert!(!requires(PeerConfigParam::EnforcedOptions {
+            send: vec![],
+            send_and_call: vec![]
+        }));
+
+        assert!(!requires(PeerConfigParam::OutboundRateLimit(Some(rate_limit(Some(1), None)))));
+        assert!(requires(PeerConfigParam::InboundRateLimit(None)));
+        // a capacity above the tokens left refills the bucket
+        assert!(requires(PeerConfigParam::InboundRateLimit(Some(rate_limit(Some(601), None)))));
+        assert!(!requires(PeerConfigParam::InboundRateLimit(Some(rate_limit(Some(600), None)))));
+
+        let sender_rate_limit = |capacity, refill_per_second| {
+            PeerConfigParam::SenderRateLimit(Some(SenderRateLimitConfig {
+                capacity,
+                refill_per_second,
+            }))
+        };
+        assert!(requires(sender_rate_limit(101, 1)));
+        assert!(requires(sender_rate_limit(100, 2)));
+        assert!(!requires(sender_rate_limit(100, 1)));
+        assert!(requires(PeerConfigParam::SenderRateLimit(None)));
+
+        let circuit_breaker = |threshold, window| {
+            PeerConfigParam::InboundCircuitBreaker(Some(CircuitBreakerParams { threshold, window }))
+        };
+        assert!(requires(circuit_breaker(1_001, 60)));
+        assert!(requires(circuit_breaker(1_000, 59)));
+        assert!(!requires(circuit_breaker(1_000, 60)));
+        assert!(!requires(circuit_breaker(500, 120)));
+        assert!(requires(PeerConfigParam::InboundCircuitBreaker(None)));
+    }
+
+    #[test]
+    fn test_new_peer_requires_timelock() {
+        let oft_store = oft_store();
+        let peer = PeerConfig::deserialize(&mut &vec![0u8; PeerConfig::INIT_SPACE][..]).unwrap();
+        let requires = |param: PeerConfigParam| param.requires_timelock(&peer, &oft_store);
+
+        // adding limits to a peer without any is immediate
+        assert!(!requires(PeerConfigParam::OutboundRateLimit(Some(rate_limit(Some(1), None)))));
+        assert!(!requires(PeerConfigParam::SenderRateLimit(Some(SenderRateLimitConfig {
+            capacity: u64::MAX,
+            refill_per_second: u64::MAX,
+        }))));
+        assert!(!requires(PeerConfigParam::InboundCircuitBreaker(Some(CircuitBreakerParams {
+            threshold: u64::MAX,
+            window: 1,
+        }))));
+        // removing absent limits is a no-op
+        assert!(!requires(PeerConfigParam::OutboundRateLimit(None)));
+        assert!(!requires(PeerConfigParam::InboundCircuitBreaker(None)));
+        assert!(!requires(PeerConfigParam::NativeFee(None)));
+        assert!(requires(PeerConfigParam::NativeFee(Some(1))));
+    }
+
+    #[test]
+    fn test_max_timelock_delay() {
+        let mut oft_store = oft_store();
+        oft_store.version = ACCOUNT_VERSION;
+        let mut data = vec![0u8; 8 + OFTStore::INIT_SPACE];
+        oft_store.try_serialize(&mut &mut data[..]).unwrap();
+        let (key, owner, mut lamports) = (Pubkey::new_unique(), oft::ID, 0);
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+        let mut oft_store = Account::<OFTStore>::try_from(&info).unwrap();
+        let mut set_delay = |delay| {
+            SetOFTConfig::update_config(
+                &mut oft_store,
+                &[],
+                &SetOFTConfigParams::TimelockDelay(delay),
+            )
+        };
+
+        assert!(set_delay(MAX_TIMELOCK_DELAY + 1).is_err());
+        assert!(set_delay(u64::MAX).is_err());
+        assert!(set_delay(MAX_TIMELOCK_DELAY).is_ok());
+        assert_eq!(oft_store.timelock_delay, MAX_TIMELOCK_DELAY);
+    }
+}
@@ -26,6 +26,12 @@ interface MasterArgs {
     oftProgramId?: string
     tokenProgram?: string
     computeUnitPriceScaleFactor?: number
+    /** Solana only: token account of the referrer, paid a share of the OFT fee */
+    referrer?: string
+    /** Solana only: share of the OFT fee paid to the referrer, in bps */
+    referralFeeBps?: number
+    /** Solana only: `to` is a token account instead of a wallet, the peer has to accept it */
+    toTokenAccount?: boolean
 }
 
 task('lz:oft:send', 'Sends OFT tokens cross‐chain from any supported chain')
@@ -54,6 +60,9 @@ task('lz:oft:send', 'Sends OFT tokens cross‐chain from any supported chain')
     .addOptionalParam('oftProgramId', 'Solana only: override the OFT program ID (base58)', undefined, types.string)
     .addOptionalParam('tokenProgram', 'Solana Token Program pubkey', undefined, types.string)
     .addOptionalParam('computeUnitPriceScaleFactor', 'Solana compute unit price scale factor', 4, types.float)
+    .addOptionalParam('referrer', 'Solana only: token account of the referrer', undefined, types.string)
+    .addOptionalParam('referralFeeBps', 'Solana only: share of the OFT fee paid to the referrer', 0, types.int)
+    .addFlag('toTokenAccount', 'Solana only: the recipient is a token account instead of a wallet')
     .setAction(async (args: MasterArgs, hre: HardhatRuntimeEnvironment) => {
         const chainType = endpointIdToChainType(args.srcEid)
         let result: SendResult
@@ -14,3 +14,4 @@ import './solana/getPrioFees'
 import './solana/base58'
 import './solana/setInboundRateLimit'
 import './solana/setOutboundRateLimit'
+import './solana/migrateAccounts'
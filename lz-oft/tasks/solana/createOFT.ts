@@ -26,6 +26,7 @@ import { EndpointId } from '@layerzerolabs/lz-definitions'
 import { OFT_DECIMALS as DEFAULT_SHARED_DECIMALS, oft } from '@layerzerolabs/oft-v2-solana-sdk'
 
 import { checkMultisigSigners, createMintAuthorityMultisig } from './multisig'
+import { ACCOUNT_VERSION, fetchOftStore, withFeeVault } from './oftProgram'
 
 import {
     TransactionType,
@@ -310,19 +311,23 @@ task('lz:oft:solana:create', 'Mints new SPL Token and creates new OFT Store acco
 
             const lockboxSigner = createSignerFromKeypair({ eddsa: eddsa }, lockBox)
             let txBuilder = transactionBuilder().add(
-                oft.initOft(
-                    {
-                        payer: umiWalletSigner,
-                        admin: umiWalletKeyPair.publicKey,
-                        mint: mint.publicKey,
-                        escrow: lockboxSigner,
-                    },
-                    oft.types.OFTType.Native,
-                    sharedDecimals,
-                    {
-                        oft: programId,
-                        token: tokenProgramId,
-                    }
+                withFeeVault(
+                    oft.initOft(
+                        {
+                            payer: umiWalletSigner,
+                            admin: umiWalletKeyPair.publicKey,
+                            mint: mint.publicKey,
+                            escrow: lockboxSigner,
+                        },
+                        oft.types.OFTType.Native,
+                        sharedDecimals,
+                        {
+                            oft: programId,
+                            token: tokenProgramId,
+                        }
+                    ),
+                    programId,
+                    oftStorePda
                 )
             )
             txBuilder = await addComputeUnitInstructions(
@@ -336,6 +341,9 @@ task('lz:oft:solana:create', 'Mints new SPL Token and creates new OFT Store acco
             )
             const { signature } = await txBuilder.sendAndConfirm(umi)
             console.log(`initOftTx: ${getExplorerTxLink(bs58.encode(signature), isTestnet)}`)
+            // the store is written in the current layout, which the SDK can not decode
+            const { version } = await fetchOftStore(connection, oftStorePda)
+            if (version !== ACCOUNT_VERSION) throw new Error(`Unexpected OFTStore version ${version}`)
 
             if (!isMABA) {
                 let txBuilder = transactionBuilder()
@@ -8,6 +8,8 @@ import { types as devtoolsTypes } from '@layerzerolabs/devtools-evm-hardhat'
 import { EndpointId } from '@layerzerolabs/lz-definitions'
 import { OFT_DECIMALS, oft } from '@layerzerolabs/oft-v2-solana-sdk'
 
+import { ACCOUNT_VERSION, fetchOftStore, withFeeVault } from './oftProgram'
+
 import {
     TransactionType,
     addComputeUnitInstructions,
@@ -67,19 +69,23 @@ task('lz:oft-adapter:solana:create', 'Creates new OFT Adapter (OFT Store PDA)')
             const mintAuthority = mintPDA.mintAuthority
 
             let txBuilder = transactionBuilder().add(
-                oft.initOft(
-                    {
-                        payer: createSignerFromKeypair({ eddsa: eddsa }, umiWalletKeyPair),
-                        admin: umiWalletKeyPair.publicKey,
-                        mint: mint,
-                        escrow: createSignerFromKeypair({ eddsa: eddsa }, lockBox),
-                    },
-                    oft.types.OFTType.Adapter,
-                    OFT_DECIMALS,
-                    {
-                        oft: programId,
-                        token: tokenProgram ? publicKey(tokenProgram) : undefined,
-                    }
+                withFeeVault(
+                    oft.initOft(
+                        {
+                            payer: createSignerFromKeypair({ eddsa: eddsa }, umiWalletKeyPair),
+                            admin: umiWalletKeyPair.publicKey,
+                            mint: mint,
+                            escrow: createSignerFromKeypair({ eddsa: eddsa }, lockBox),
+                        },
+                        oft.types.OFTType.Adapter,
+                        OFT_DECIMALS,
+                        {
+                            oft: programId,
+                            token: tokenProgram ? publicKey(tokenProgram) : undefined,
+                        }
+                    ),
+                    programId,
+                    oftStorePda
                 )
             )
             txBuilder = await addComputeUnitInstructions(
@@ -93,6 +99,9 @@ task('lz:oft-adapter:solana:create', 'Creates new OFT Adapter (OFT Store PDA)')
             )
             const { signature } = await txBuilder.sendAndConfirm(umi)
             console.log(`initOftTx: ${getExplorerTxLink(bs58.encode(signature), eid == EndpointId.SOLANA_V2_TESTNET)}`)
+            // the store is written in the current layout, which the SDK can not decode
+            const { version } = await fetchOftStore(connection, oftStorePda)
+            if (version !== ACCOUNT_VERSION) throw new Error(`Unexpected OFTStore version ${version}`)
 
             saveSolanaDeployment(
                 eid,
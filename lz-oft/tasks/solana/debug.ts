@@ -1,6 +1,6 @@
 import { fetchMint } from '@metaplex-foundation/mpl-toolbox'
-import { publicKey, unwrapOption } from '@metaplex-foundation/umi'
-import { toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters'
+import { PublicKey as UmiPublicKey, publicKey, unwrapOption } from '@metaplex-foundation/umi'
+import { fromWeb3JsPublicKey, toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters'
 import { Keypair, PublicKey } from '@solana/web3.js'
 import { task } from 'hardhat/config'
 
@@ -8,12 +8,20 @@ import { OmniPoint, denormalizePeer } from '@layerzerolabs/devtools'
 import { types } from '@layerzerolabs/devtools-evm-hardhat'
 import { EndpointId, getNetworkForChainId } from '@layerzerolabs/lz-definitions'
 import { EndpointPDADeriver, EndpointProgram } from '@layerzerolabs/lz-solana-sdk-v2'
-import { OftPDA, oft } from '@layerzerolabs/oft-v2-solana-sdk'
 import { EndpointV2 } from '@layerzerolabs/protocol-devtools-solana'
 
 import { getSolanaReceiveConfig, getSolanaSendConfig } from '../common/taskHelper'
 import { DebugLogger, createSolanaConnectionFactory, decodeLzReceiveOptions, uint8ArrayToHex } from '../common/utils'
 
+import {
+    ACCOUNT_VERSION,
+    OFTType,
+    OftStoreAccount,
+    decodeOftStore,
+    derivePeer,
+    safeFetchPeerConfig,
+} from './oftProgram'
+
 import { deriveConnection, getSolanaDeployment } from './index'
 
 const DEBUG_ACTIONS = {
@@ -67,9 +75,13 @@ task('lz:oft:solana:debug', 'Manages OFTStore and OAppRegistry information')
         const { umi, connection } = await deriveConnection(eid, true)
         const oftStore = getOftStore(eid, oftStoreArg)
 
-        let oftStoreInfo
+        let oftStoreInfo: OftStoreAccount
+        let programId: UmiPublicKey
         try {
-            oftStoreInfo = await oft.accounts.fetchOFTStore(umi, oftStore)
+            const oftStoreAccount = await connection.getAccountInfo(toWeb3JsPublicKey(oftStore))
+            if (!oftStoreAccount) throw new Error('account not found')
+            oftStoreInfo = decodeOftStore(oftStoreAccount.data)
+            programId = fromWeb3JsPublicKey(oftStoreAccount.owner)
         } catch (e) {
             console.error(`Failed to fetch OFTStore at ${oftStore.toString()}:`, e)
             return
@@ -89,12 +101,11 @@ task('lz:oft:solana:debug', 'Manages OFTStore and OAppRegistry information')
             return
         }
 
-        const oftDeriver = new OftPDA(oftStoreInfo.header.owner)
-
         const printOftStore = async () => {
             DebugLogger.header('OFT Store Information')
-            DebugLogger.keyValue('Owner', oftStoreInfo.header.owner)
-            DebugLogger.keyValue('OFT Type', oft.types.OFTType[oftStoreInfo.oftType])
+            DebugLogger.keyValue('Owner', programId)
+            DebugLogger.keyValue('Account Version', printVersion(oftStoreInfo.version))
+            DebugLogger.keyValue('OFT Type', OFTType[oftStoreInfo.oftType])
             DebugLogger.keyValue('Admin', oftStoreInfo.admin)
             DebugLogger.keyValue('Token Mint', oftStoreInfo.tokenMint)
             DebugLogger.keyValue('Token Escrow', oftStoreInfo.tokenEscrow)
@@ -137,10 +148,7 @@ task('lz:oft:solana:debug', 'Manages OFTStore and OAppRegistry information')
         }
 
         const printPeerConfigs = async () => {
-            const peerConfigs = dstEids.map((dstEid) => {
-                const peerConfig = oftDeriver.peer(oftStore, dstEid)
-                return publicKey(peerConfig)
-            })
+            const peerConfigs = dstEids.map((dstEid) => derivePeer(programId, oftStore, dstEid))
             const mockKeypair = new Keypair()
             const point: OmniPoint = {
                 eid,
@@ -154,7 +162,9 @@ task('lz:oft:solana:debug', 'Manages OFTStore and OAppRegistry information')
 
             DebugLogger.header('Peer Configurations')
 
-            const peerConfigInfos = await oft.accounts.safeFetchAllPeerConfig(umi, peerConfigs)
+            const peerConfigInfos = await Promise.all(
+                peerConfigs.map((peerConfig) => safeFetchPeerConfig(connection, peerConfig))
+            )
             for (let index = 0; index < dstEids.length; index++) {
                 const dstEid = dstEids[index]
                 const info = peerConfigInfos[index]
@@ -168,6 +178,7 @@ task('lz:oft:solana:debug', 'Manages OFTStore and OAppRegistry information')
                 if (info) {
                     // Existing PeerConfig info
                     DebugLogger.keyValue('PeerConfig Account', peerConfigs[index].toString())
+                    DebugLogger.keyValue('Account Version', printVersion(info.version))
                     DebugLogger.keyValue('Peer Address', denormalizePeer(info.peerAddress, dstEid))
                     DebugLogger.keyHeader('Enforced Options')
                     DebugLogger.keyValue(
@@ -223,6 +234,10 @@ task('lz:oft:solana:debug', 'Manages OFTStore and OAppRegistry information')
         }
     })
 
+// legacy accounts can be read but not written until lz:oft:solana:migrate-accounts has run
+const printVersion = (version: number) =>
+    version === ACCOUNT_VERSION ? `${version}` : `${version} (run lz:oft:solana:migrate-accounts)`
+
 function printOAppReceiveConfigs(
     oAppReceiveConfig: Awaited<ReturnType<typeof getSolanaReceiveConfig>>,
     peerChainName: string
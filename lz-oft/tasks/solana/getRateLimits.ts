@@ -1,14 +1,13 @@
-import { mplToolbox } from '@metaplex-foundation/mpl-toolbox'
 import { publicKey } from '@metaplex-foundation/umi'
-import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
 import { task } from 'hardhat/config'
 
 import { types } from '@layerzerolabs/devtools-evm-hardhat'
 import { EndpointId } from '@layerzerolabs/lz-definitions'
-import { OftPDA, accounts } from '@layerzerolabs/oft-v2-solana-sdk'
 
 import { createSolanaConnectionFactory } from '../common/utils'
 
+import { derivePeer, fetchPeerConfig } from './oftProgram'
+
 interface Args {
     mint: string
     eid: EndpointId
@@ -26,10 +25,9 @@ task('lz:oft:solana:get-rate-limits', 'Gets the Solana inbound / outbound rate l
     .setAction(async (taskArgs: Args, _) => {
         const connectionFactory = createSolanaConnectionFactory()
         const connection = await connectionFactory(taskArgs.eid)
-        const umi = createUmi(connection.rpcEndpoint).use(mplToolbox())
 
-        const [peer] = new OftPDA(publicKey(taskArgs.programId)).peer(publicKey(taskArgs.oftStore), taskArgs.dstEid)
-        const peerInfo = await accounts.fetchPeerConfig({ rpc: umi.rpc }, peer)
+        const peer = derivePeer(publicKey(taskArgs.programId), publicKey(taskArgs.oftStore), taskArgs.dstEid)
+        const peerInfo = await fetchPeerConfig(connection, peer)
         console.log(`Peer info between ${taskArgs.eid} and ${taskArgs.dstEid}`)
         console.dir({ peerInfo }, { depth: null })
     })
@@ -0,0 +1,58 @@
+import { publicKey } from '@metaplex-foundation/umi'
+import { toWeb3JsKeypair } from '@metaplex-foundation/umi-web3js-adapters'
+import { Transaction, sendAndConfirmTransaction } from '@solana/web3.js'
+import { task } from 'hardhat/config'
+
+import { types } from '@layerzerolabs/devtools-evm-hardhat'
+import { EndpointId } from '@layerzerolabs/lz-definitions'
+
+import { ACCOUNT_VERSION, derivePeer, fetchOftStore, migrateAccount, safeFetchPeerConfig } from './oftProgram'
+
+import { deriveConnection, getExplorerTxLink, getSolanaDeployment } from './index'
+
+interface Args {
+    eid: EndpointId
+    oftStore?: string
+    programId?: string
+    dstEids: EndpointId[]
+}
+
+// The upgraded program reads the accounts of the baseline program, but fails any write to them with
+// AccountNotMigrated. Run this right after the upgrade: until then the OFT can not send, receive or
+// be configured.
+task('lz:oft:solana:migrate-accounts', 'Migrates the OFTStore and its peers to the current account layout')
+    .addParam('eid', 'Solana mainnet (30168) or testnet (40168)', undefined, types.eid)
+    .addOptionalParam('oftStore', 'The OFTStore account, defaults to the deployment', undefined, types.string)
+    .addOptionalParam('programId', 'The OFT Program id, defaults to the deployment', undefined, types.string)
+    .addParam('dstEids', 'The eids of all the peers (comma-separated list)', undefined, types.csv)
+    .setAction(async ({ eid, oftStore: oftStoreArg, programId: programIdArg, dstEids }: Args) => {
+        const { connection, umiWalletKeyPair } = await deriveConnection(eid)
+        const signer = toWeb3JsKeypair(umiWalletKeyPair)
+        const oftStore = publicKey(oftStoreArg ?? getSolanaDeployment(eid).oftStore)
+        const programId = publicKey(programIdArg ?? getSolanaDeployment(eid).programId)
+        const isTestnet = eid == EndpointId.SOLANA_V2_TESTNET
+
+        const migrate = async (remoteEid?: number) => {
+            const tx = new Transaction().add(
+                migrateAccount(umiWalletKeyPair.publicKey, programId, oftStore, remoteEid)
+            )
+            const signature = await sendAndConfirmTransaction(connection, tx, [signer])
+            console.log(`migrateAccount ${remoteEid ?? 'OFTStore'}: ${getExplorerTxLink(signature, isTestnet)}`)
+        }
+
+        // every migration of a peer migrates the oft_store along
+        let oftStoreMigrated = (await fetchOftStore(connection, oftStore)).version === ACCOUNT_VERSION
+        for (const dstEid of dstEids) {
+            const peer = await safeFetchPeerConfig(connection, derivePeer(programId, oftStore, dstEid))
+            if (!peer) {
+                console.warn(`No PeerConfig account found for ${dstEid}`)
+            } else if (peer.version !== ACCOUNT_VERSION) {
+                await migrate(dstEid)
+                oftStoreMigrated = true
+            }
+        }
+        if (!oftStoreMigrated) {
+            await migrate()
+        }
+        console.log('The OFTStore and the peers are at the current account version')
+    })
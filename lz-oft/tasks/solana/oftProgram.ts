@@ -0,0 +1,712 @@
+import { createHash } from 'node:crypto'
+
+import { AccountMeta, Option, PublicKey, WrappedInstruction, none, publicKeyBytes, some } from '@metaplex-foundation/umi'
+import { createWeb3JsEddsa } from '@metaplex-foundation/umi-eddsa-web3js'
+import {
+    Serializer,
+    array,
+    bool,
+    bytes,
+    dataEnum,
+    i64,
+    option,
+    publicKey as publicKeySerializer,
+    scalarEnum,
+    struct,
+    u16,
+    u32,
+    u64,
+    u8,
+    unit,
+} from '@metaplex-foundation/umi/serializers'
+import { fromWeb3JsPublicKey, toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters'
+import {
+    ComputeBudgetProgram,
+    Connection,
+    SystemProgram,
+    Transaction,
+    TransactionInstruction,
+} from '@solana/web3.js'
+
+import { EndpointProgram } from '@layerzerolabs/lz-solana-sdk-v2'
+
+// The SDK builds the instructions of the baseline OFT program. The helpers below adapt them to the
+// accounts and args the program has gained since, or build them when the args changed.
+
+const FEE_VAULT_SEED = 'FeeVault'
+const FEE_EXEMPTION_SEED = 'FeeExemption'
+const SENDER_RATE_LIMIT_SEED = 'SenderRateLimit'
+const OFT_SEED = 'OFT'
+const PEER_SEED = 'Peer'
+const EVENT_AUTHORITY_SEED = '__event_authority'
+
+const eddsa = createWeb3JsEddsa()
+
+const findPda = (programId: PublicKey, seeds: Uint8Array[]): PublicKey => eddsa.findPda(programId, seeds)[0]
+
+const eidBytes = (eid: number): Uint8Array => u32({ endian: 'be' }).serialize(eid)
+
+/**
+ * Derive the fee vault holding the native fees of an OFT Store.
+ * @param programId {PublicKey}
+ * @param oftStore {PublicKey}
+ */
+export const deriveFeeVault = (programId: PublicKey, oftStore: PublicKey): PublicKey =>
+    findPda(programId, [Buffer.from(FEE_VAULT_SEED), publicKeyBytes(oftStore)])
+
+/**
+ * Derive the fee exemption of a sender, which only exists if the admin exempted it.
+ */
+export const deriveFeeExemption = (programId: PublicKey, oftStore: PublicKey, sender: PublicKey): PublicKey =>
+    findPda(programId, [Buffer.from(FEE_EXEMPTION_SEED), publicKeyBytes(oftStore), publicKeyBytes(sender)])
+
+/**
+ * Derive the rate limit bucket of a sender, which only exists if the peer has a sender rate limit.
+ */
+export const deriveSenderRateLimit = (programId: PublicKey, peer: PublicKey, sender: PublicKey): PublicKey =>
+    findPda(programId, [Buffer.from(SENDER_RATE_LIMIT_SEED), publicKeyBytes(peer), publicKeyBytes(sender)])
+
+export const deriveOftStore = (programId: PublicKey, escrowSeed: PublicKey): PublicKey =>
+    findPda(programId, [Buffer.from(OFT_SEED), publicKeyBytes(escrowSeed)])
+
+export const derivePeer = (programId: PublicKey, oftStore: PublicKey, remoteEid: number): PublicKey =>
+    findPda(programId, [Buffer.from(PEER_SEED), publicKeyBytes(oftStore), eidBytes(remoteEid)])
+
+/**
+ * Insert an account into the accounts of an SDK instruction at `index`.
+ */
+export const insertAccount = (ix: WrappedInstruction, index: number, account: AccountMeta): WrappedInstruction => {
+    const keys = [...ix.instruction.keys]
+    keys.splice(index, 0, account)
+    return { ...ix, instruction: { ...ix.instruction, keys } }
+}
+
+/**
+ * init_oft funds the fee vault, which follows the lz_receive_types_accounts.
+ * @param initOftIx {WrappedInstruction} from oft.initOft
+ * @param programId {PublicKey}
+ * @param oftStore {PublicKey}
+ */
+export const withFeeVault = (
+    initOftIx: WrappedInstruction,
+    programId: PublicKey,
+    oftStore: PublicKey
+): WrappedInstruction =>
+    insertAccount(initOftIx, 3, { pubkey: deriveFeeVault(programId, oftStore), isSigner: false, isWritable: true })
+
+// ============================== Send and quote ==============================
+
+export interface ReferralParams {
+    referrer: PublicKey // token account of the referrer
+    feeBps: number // share of the OFT fee
+}
+
+export interface SendParams {
+    dstEid: number
+    to: Uint8Array
+    amountLd: bigint
+    minAmountLd: bigint
+    options: Uint8Array
+    composeMsg: Uint8Array | null
+    nativeFee: bigint
+    lzTokenFee: bigint
+    referral: ReferralParams | null
+    toTokenAccount: boolean // `to` is a token account instead of a wallet
+}
+
+export interface QuoteSendParams {
+    dstEid: number
+    to: Uint8Array
+    amountLd: bigint
+    minAmountLd: bigint
+    options: Uint8Array
+    composeMsg: Uint8Array | null
+    payInLzToken: boolean
+    toTokenAccount: boolean
+    sender: PublicKey // the signer of the send to quote for
+}
+
+const instructionDiscriminator = (name: string): Uint8Array =>
+    createHash('sha256').update(`global:${name}`).digest().subarray(0, 8)
+
+const optionalBytes = option(bytes({ size: u32() }))
+
+const sendParamsSerializer: Serializer<SendParams> = struct([
+    ['dstEid', u32()],
+    ['to', bytes({ size: 32 })],
+    ['amountLd', u64()],
+    ['minAmountLd', u64()],
+    ['options', bytes({ size: u32() })],
+    ['composeMsg', optionalBytes],
+    ['nativeFee', u64()],
+    ['lzTokenFee', u64()],
+    [
+        'referral',
+        option(
+            struct<ReferralParams>([
+                ['referrer', publicKeySerializer()],
+                ['feeBps', u16()],
+            ])
+        ),
+    ],
+    ['toTokenAccount', bool()],
+]) as unknown as Serializer<SendParams>
+
+const quoteSendParamsSerializer: Serializer<QuoteSendParams> = struct([
+    ['dstEid', u32()],
+    ['to', bytes({ size: 32 })],
+    ['amountLd', u64()],
+    ['minAmountLd', u64()],
+    ['options', bytes({ size: u32() })],
+    ['composeMsg', optionalBytes],
+    ['payInLzToken', bool()],
+    ['toTokenAccount', bool()],
+    ['sender', publicKeySerializer()],
+]) as unknown as Serializer<QuoteSendParams>
+
+const messagingFeeSerializer = struct<{ nativeFee: bigint; lzTokenFee: bigint }>([
+    ['nativeFee', u64()],
+    ['lzTokenFee', u64()],
+])
+
+const encode = <T>(name: string, serializer: Serializer<T>, params: T): Buffer =>
+    Buffer.concat([instructionDiscriminator(name), serializer.serialize(params)])
+
+const meta = (pubkey: PublicKey, isWritable = false): AccountMeta => ({ pubkey, isSigner: false, isWritable })
+
+const toWeb3JsInstruction = (programId: PublicKey, keys: AccountMeta[], data: Buffer): TransactionInstruction =>
+    new TransactionInstruction({
+        programId: toWeb3JsPublicKey(programId),
+        keys: keys.map(({ pubkey, isSigner, isWritable }) => ({
+            pubkey: toWeb3JsPublicKey(pubkey),
+            isSigner,
+            isWritable,
+        })),
+        data,
+    })
+
+const existingAccount = async (connection: Connection, pubkey: PublicKey): Promise<PublicKey | undefined> =>
+    (await connection.getAccountInfo(toWeb3JsPublicKey(pubkey))) ? pubkey : undefined
+
+export interface OftAccounts {
+    programId: PublicKey
+    oftStore: PublicKey
+    tokenMint: PublicKey
+    peerAddress: Uint8Array
+    endpointProgram?: PublicKey
+}
+
+const endpointFor = (accounts: OftAccounts) =>
+    new EndpointProgram.Endpoint(
+        accounts.endpointProgram ? toWeb3JsPublicKey(accounts.endpointProgram) : EndpointProgram.PROGRAM_ID
+    )
+
+const packetPath = (accounts: OftAccounts, dstEid: number) => ({
+    dstEid,
+    sender: toWeb3JsPublicKey(accounts.oftStore),
+    receiver: accounts.peerAddress,
+})
+
+/**
+ * Quote the messaging fee of a send by simulating quote_send.
+ */
+export async function quoteSend(
+    connection: Connection,
+    payer: PublicKey,
+    accounts: OftAccounts,
+    params: QuoteSendParams
+): Promise<{ nativeFee: bigint; lzTokenFee: bigint }> {
+    const { programId, oftStore, tokenMint } = accounts
+    const endpoint = endpointFor(accounts)
+    const msgLib = await endpoint.getSendLibraryProgram(connection, toWeb3JsPublicKey(oftStore), params.dstEid)
+    const endpointAccounts = await endpoint.getQuoteIXAccountMetaForCPI(
+        connection,
+        toWeb3JsPublicKey(payer),
+        packetPath(accounts, params.dstEid),
+        msgLib
+    )
+    const feeExemption = await existingAccount(connection, deriveFeeExemption(programId, oftStore, params.sender))
+    const keys = [
+        meta(oftStore),
+        meta(derivePeer(programId, oftStore, params.dstEid)),
+        meta(tokenMint),
+        meta(feeExemption ?? programId),
+    ]
+    const ix = toWeb3JsInstruction(programId, keys, encode('quote_send', quoteSendParamsSerializer, params))
+    ix.keys.push(...endpointAccounts)
+
+    const tx = new Transaction().add(ComputeBudgetProgram.setComputeUnitLimit({ units: 1_000_000 }), ix)
+    tx.feePayer = toWeb3JsPublicKey(payer)
+    tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash
+    const { value } = await connection.simulateTransaction(tx)
+    if (value.err || !value.returnData) {
+        throw new Error(`quote_send failed: ${JSON.stringify(value.err)}\n${value.logs?.join('\n')}`)
+    }
+    return messagingFeeSerializer.deserialize(Buffer.from(value.returnData.data[0], 'base64'))[0]
+}
+
+/**
+ * Build the send instruction. The fee exemption and the sender rate limit of the signer are passed
+ * when they exist, the fee vault in case the peer charges a native fee.
+ */
+export async function send(
+    connection: Connection,
+    signer: PublicKey,
+    accounts: OftAccounts & { tokenEscrow: PublicKey; tokenSource: PublicKey; tokenProgram: PublicKey },
+    params: SendParams
+): Promise<TransactionInstruction> {
+    const { programId, oftStore, tokenMint, tokenEscrow, tokenSource, tokenProgram } = accounts
+    const endpoint = endpointFor(accounts)
+    const msgLib = await endpoint.getSendLibraryProgram(connection, toWeb3JsPublicKey(oftStore), params.dstEid)
+    const endpointAccounts = await endpoint.getSendIXAccountMetaForCPI(
+        connection,
+        toWeb3JsPublicKey(signer),
+        packetPath(accounts, params.dstEid),
+        msgLib
+    )
+    const peer = derivePeer(programId, oftStore, params.dstEid)
+    const feeExemption = await existingAccount(connection, deriveFeeExemption(programId, oftStore, signer))
+    const senderRateLimit = await existingAccount(connection, deriveSenderRateLimit(programId, peer, signer))
+    const keys: AccountMeta[] = [
+        { pubkey: signer, isSigner: true, isWritable: true },
+        meta(peer, true),
+        meta(oftStore, true),
+        meta(tokenSource, true),
+        meta(tokenEscrow, true),
+        meta(tokenMint, true),
+        meta(tokenProgram),
+        // optional accounts are replaced by the program id when absent
+        meta(feeExemption ?? programId),
+        meta(deriveFeeVault(programId, oftStore), true),
+        meta(fromWeb3JsPublicKey(SystemProgram.programId)),
+        params.referral ? meta(params.referral.referrer, true) : meta(programId),
+        senderRateLimit ? meta(senderRateLimit, true) : meta(programId),
+        // #[event_cpi]
+        meta(findPda(programId, [Buffer.from(EVENT_AUTHORITY_SEED)])),
+        meta(programId),
+    ]
+    const ix = toWeb3JsInstruction(programId, keys, encode('send', sendParamsSerializer, params))
+    ix.keys.push(...endpointAccounts)
+    return ix
+}
+
+// ============================== Accounts ==============================
+
+// The OFTStore and the PeerConfig start with a version byte since ACCOUNT_VERSION 1. Accounts of the
+// baseline program have the version 0 layout, recognized by their size, and can only be read until
+// migrate_account has rewritten them, see migrateAccounts.ts.
+export const ACCOUNT_VERSION = 1
+const OFT_STORE_V0_LEN = 8 + 215
+const PEER_CONFIG_V0_LEN = 8 + 1646
+const DISCRIMINATOR_LEN = 8
+
+export enum OFTType {
+    Native,
+    Adapter,
+}
+
+export type RateLimiterType = { __kind: 'TokenBucket' } | { __kind: 'Window'; window: bigint }
+
+export interface RateLimiter {
+    capacity: bigint
+    tokens: bigint
+    refillPerSecond: bigint
+    lastRefillTime: bigint
+    limiterType: RateLimiterType
+}
+
+export interface OFTLimits {
+    minAmountLd: bigint
+    maxAmountLd: bigint
+}
+
+export interface OftStoreAccount {
+    version: number
+    oftType: OFTType
+    ld2sdRate: bigint
+    tokenMint: PublicKey
+    tokenEscrow: PublicKey
+    endpointProgram: PublicKey
+    bump: number
+    tvlLd: bigint
+    totalFeeLd: bigint
+    admin: PublicKey
+    defaultFeeBps: number
+    maxReferralFeeBps: number
+    defaultOftLimits: OFTLimits
+    paused: boolean
+    pausedUntil: Option<bigint>
+    pauser: Option<PublicKey>
+    unpauser: Option<PublicKey>
+    guardian: Option<PublicKey>
+    timelockDelay: bigint
+    changeNonce: bigint
+    outboundRateLimiter: Option<RateLimiter>
+    inboundRateLimiter: Option<RateLimiter>
+    nativeSol: boolean
+    escrowSeed: PublicKey
+}
+
+export interface CircuitBreaker {
+    threshold: bigint
+    window: bigint
+    volume: bigint
+    lastUpdateTime: bigint
+    approvedGuids: Uint8Array[]
+}
+
+export interface FeeSchedule {
+    tiers: { minAmountLd: bigint; feeBps: number }[]
+    minFeeLd: bigint
+    maxFeeLd: bigint
+}
+
+export interface TransferStats {
+    totalSentLd: bigint
+    totalReceivedLd: bigint
+    sentCount: bigint
+    receivedCount: bigint
+    totalFeeLd: bigint
+}
+
+export interface PeerConfigAccount {
+    version: number
+    peerAddress: Uint8Array
+    enforcedOptions: { send: Uint8Array; sendAndCall: Uint8Array }
+    outboundRateLimiter: Option<RateLimiter>
+    inboundRateLimiter: Option<RateLimiter>
+    feeBps: Option<number>
+    bump: number
+    outboundPaused: boolean
+    outboundPausedUntil: Option<bigint>
+    inboundPaused: boolean
+    inboundPausedUntil: Option<bigint>
+    inboundCircuitBreaker: Option<CircuitBreaker>
+    oftLimits: Option<OFTLimits>
+    feeSchedule: Option<FeeSchedule>
+    nativeFee: Option<bigint>
+    stats: TransferStats
+    senderRateLimit: Option<{ capacity: bigint; refillPerSecond: bigint }>
+    ataRentFeeLd: Option<bigint>
+    tokenAccountDest: boolean
+}
+
+// the limiters of version 0 are token buckets
+const rateLimiterV0Serializer = struct<Omit<RateLimiter, 'limiterType'>>([
+    ['capacity', u64()],
+    ['tokens', u64()],
+    ['refillPerSecond', u64()],
+    ['lastRefillTime', u64()],
+])
+
+const rateLimiterTypeSerializer = dataEnum<RateLimiterType>([
+    ['TokenBucket', unit()],
+    ['Window', struct<{ window: bigint }>([['window', u64()]])],
+]) as Serializer<RateLimiterType>
+
+const rateLimiterSerializer = struct<RateLimiter>([
+    ['capacity', u64()],
+    ['tokens', u64()],
+    ['refillPerSecond', u64()],
+    ['lastRefillTime', u64()],
+    ['limiterType', rateLimiterTypeSerializer],
+])
+
+const oftLimitsSerializer = struct<OFTLimits>([
+    ['minAmountLd', u64()],
+    ['maxAmountLd', u64()],
+])
+
+const oftStoreSerializer = struct<OftStoreAccount>([
+    ['version', u8()],
+    ['oftType', scalarEnum(OFTType)],
+    ['ld2sdRate', u64()],
+    ['tokenMint', publicKeySerializer()],
+    ['tokenEscrow', publicKeySerializer()],
+    ['endpointProgram', publicKeySerializer()],
+    ['bump', u8()],
+    ['tvlLd', u64()],
+    ['totalFeeLd', u64()],
+    ['admin', publicKeySerializer()],
+    ['defaultFeeBps', u16()],
+    ['maxReferralFeeBps', u16()],
+    ['defaultOftLimits', oftLimitsSerializer],
+    ['paused', bool()],
+    ['pausedUntil', option(i64())],
+    ['pauser', option(publicKeySerializer())],
+    ['unpauser', option(publicKeySerializer())],
+    ['guardian', option(publicKeySerializer())],
+    ['timelockDelay', u64()],
+    ['changeNonce', u64()],
+    ['outboundRateLimiter', option(rateLimiterSerializer)],
+    ['inboundRateLimiter', option(rateLimiterSerializer)],
+    ['nativeSol', bool()],
+    ['escrowSeed', publicKeySerializer()],
+])
+
+type OftStoreV0 = Pick<
+    OftStoreAccount,
+    | 'oftType'
+    | 'ld2sdRate'
+    | 'tokenMint'
+    | 'tokenEscrow'
+    | 'endpointProgram'
+    | 'bump'
+    | 'tvlLd'
+    | 'admin'
+    | 'defaultFeeBps'
+    | 'paused'
+    | 'pauser'
+    | 'unpauser'
+>
+
+const oftStoreV0Serializer = struct<OftStoreV0>([
+    ['oftType', scalarEnum(OFTType)],
+    ['ld2sdRate', u64()],
+    ['tokenMint', publicKeySerializer()],
+    ['tokenEscrow', publicKeySerializer()],
+    ['endpointProgram', publicKeySerializer()],
+    ['bump', u8()],
+    ['tvlLd', u64()],
+    ['admin', publicKeySerializer()],
+    ['defaultFeeBps', u16()],
+    ['paused', bool()],
+    ['pauser', option(publicKeySerializer())],
+    ['unpauser', option(publicKeySerializer())],
+])
+
+const enforcedOptionsSerializer = struct<PeerConfigAccount['enforcedOptions']>([
+    ['send', bytes({ size: u32() })],
+    ['sendAndCall', bytes({ size: u32() })],
+])
+
+const peerConfigSerializer = struct<PeerConfigAccount>([
+    ['version', u8()],
+    ['peerAddress', bytes({ size: 32 })],
+    ['enforcedOptions', enforcedOptionsSerializer],
+    ['outboundRateLimiter', option(rateLimiterSerializer)],
+    ['inboundRateLimiter', option(rateLimiterSerializer)],
+    ['feeBps', option(u16())],
+    ['bump', u8()],
+    ['outboundPaused', bool()],
+    ['outboundPausedUntil', option(i64())],
+    ['inboundPaused', bool()],
+    ['inboundPausedUntil', option(i64())],
+    [
+        'inboundCircuitBreaker',
+        option(
+            struct<CircuitBreaker>([
+                ['threshold', u64()],
+                ['window', u64()],
+                ['volume', u64()],
+                ['lastUpdateTime', u64()],
+                ['approvedGuids', array(bytes({ size: 32 }))],
+            ])
+        ),
+    ],
+    ['oftLimits', option(oftLimitsSerializer)],
+    [
+        'feeSchedule',
+        option(
+            struct<FeeSchedule>([
+                [
+                    'tiers',
+                    array(
+                        struct<FeeSchedule['tiers'][number]>([
+                            ['minAmountLd', u64()],
+                            ['feeBps', u16()],
+                        ])
+                    ),
+                ],
+                ['minFeeLd', u64()],
+                ['maxFeeLd', u64()],
+            ])
+        ),
+    ],
+    ['nativeFee', option(u64())],
+    [
+        'stats',
+        struct<TransferStats>([
+            ['totalSentLd', u64()],
+            ['totalReceivedLd', u64()],
+            ['sentCount', u64()],
+            ['receivedCount', u64()],
+            ['totalFeeLd', u64()],
+        ]),
+    ],
+    [
+        'senderRateLimit',
+        option(
+            struct<{ capacity: bigint; refillPerSecond: bigint }>([
+                ['capacity', u64()],
+                ['refillPerSecond', u64()],
+            ])
+        ),
+    ],
+    ['ataRentFeeLd', option(u64())],
+    ['tokenAccountDest', bool()],
+])
+
+type PeerConfigV0 = Pick<PeerConfigAccount, 'peerAddress' | 'enforcedOptions' | 'feeBps' | 'bump'> & {
+    outboundRateLimiter: Option<Omit<RateLimiter, 'limiterType'>>
+    inboundRateLimiter: Option<Omit<RateLimiter, 'limiterType'>>
+}
+
+const peerConfigV0Serializer = struct<PeerConfigV0>([
+    ['peerAddress', bytes({ size: 32 })],
+    ['enforcedOptions', enforcedOptionsSerializer],
+    ['outboundRateLimiter', option(rateLimiterV0Serializer)],
+    ['inboundRateLimiter', option(rateLimiterV0Serializer)],
+    ['feeBps', option(u16())],
+    ['bump', u8()],
+])
+
+const toTokenBucket = (rateLimiter: Option<Omit<RateLimiter, 'limiterType'>>): Option<RateLimiter> =>
+    rateLimiter.__option === 'Some'
+        ? some({ ...rateLimiter.value, limiterType: { __kind: 'TokenBucket' } })
+        : none()
+
+/**
+ * Decode an OFTStore, with the fields added since version 0 at their defaults for a legacy account.
+ * @param data {Uint8Array} the account data, including the discriminator
+ */
+export const decodeOftStore = (data: Uint8Array): OftStoreAccount => {
+    if (data.length !== OFT_STORE_V0_LEN) {
+        return oftStoreSerializer.deserialize(data, DISCRIMINATOR_LEN)[0]
+    }
+    const [oftStore] = oftStoreV0Serializer.deserialize(data, DISCRIMINATOR_LEN)
+    return {
+        ...oftStore,
+        version: 0,
+        totalFeeLd: 0n,
+        maxReferralFeeBps: 0,
+        defaultOftLimits: { minAmountLd: 0n, maxAmountLd: 0xffff_ffff_ffff_ffffn },
+        pausedUntil: none(),
+        guardian: none(),
+        timelockDelay: 0n,
+        changeNonce: 0n,
+        outboundRateLimiter: none(),
+        inboundRateLimiter: none(),
+        nativeSol: false,
+        escrowSeed: oftStore.tokenEscrow,
+    }
+}
+
+/**
+ * Decode a PeerConfig, with the fields added since version 0 at their defaults for a legacy account.
+ * @param data {Uint8Array} the account data, including the discriminator
+ */
+export const decodePeerConfig = (data: Uint8Array): PeerConfigAccount => {
+    if (data.length !== PEER_CONFIG_V0_LEN) {
+        return peerConfigSerializer.deserialize(data, DISCRIMINATOR_LEN)[0]
+    }
+    const [peer] = peerConfigV0Serializer.deserialize(data, DISCRIMINATOR_LEN)
+    return {
+        ...peer,
+        version: 0,
+        outboundRateLimiter: toTokenBucket(peer.outboundRateLimiter),
+        inboundRateLimiter: toTokenBucket(peer.inboundRateLimiter),
+        outboundPaused: false,
+        outboundPausedUntil: none(),
+        inboundPaused: false,
+        inboundPausedUntil: none(),
+        inboundCircuitBreaker: none(),
+        oftLimits: none(),
+        feeSchedule: none(),
+        nativeFee: none(),
+        stats: { totalSentLd: 0n, totalReceivedLd: 0n, sentCount: 0n, receivedCount: 0n, totalFeeLd: 0n },
+        senderRateLimit: none(),
+        ataRentFeeLd: none(),
+        tokenAccountDest: false,
+    }
+}
+
+export async function fetchOftStore(connection: Connection, address: PublicKey): Promise<OftStoreAccount> {
+    const info = await connection.getAccountInfo(toWeb3JsPublicKey(address))
+    if (!info) throw new Error(`OFTStore ${address} not found`)
+    return decodeOftStore(info.data)
+}
+
+export async function safeFetchPeerConfig(
+    connection: Connection,
+    address: PublicKey
+): Promise<PeerConfigAccount | null> {
+    const info = await connection.getAccountInfo(toWeb3JsPublicKey(address))
+    return info ? decodePeerConfig(info.data) : null
+}
+
+export async function fetchPeerConfig(connection: Connection, address: PublicKey): Promise<PeerConfigAccount> {
+    const peer = await safeFetchPeerConfig(connection, address)
+    if (!peer) throw new Error(`PeerConfig ${address} not found`)
+    return peer
+}
+
+/**
+ * Build migrate_account, which rewrites the OFTStore, and the peer of remoteEid if passed, in the
+ * current layout. The payer funds the larger accounts.
+ */
+export const migrateAccount = (
+    payer: PublicKey,
+    programId: PublicKey,
+    oftStore: PublicKey,
+    remoteEid?: number
+): TransactionInstruction => {
+    const keys: AccountMeta[] = [
+        { pubkey: payer, isSigner: true, isWritable: true },
+        meta(oftStore, true),
+        remoteEid === undefined ? meta(programId) : meta(derivePeer(programId, oftStore, remoteEid), true),
+        meta(fromWeb3JsPublicKey(SystemProgram.programId)),
+    ]
+    const remoteEidSerializer = struct<{ remoteEid: Option<number> }>([['remoteEid', option(u32())]])
+    const data = encode('migrate_account', remoteEidSerializer, {
+        remoteEid: remoteEid === undefined ? none() : some(remoteEid),
+    })
+    return toWeb3JsInstruction(programId, keys, data)
+}
+
+// ============================== Config ==============================
+
+export interface RateLimitParams {
+    refillPerSecond: bigint | null
+    capacity: bigint | null
+    limiterType: RateLimiterType | null // null keeps the current type, a token bucket for a new limiter
+}
+
+const rateLimitParamsSerializer = struct<RateLimitParams>([
+    ['refillPerSecond', option(u64())],
+    ['capacity', option(u64())],
+    ['limiterType', option(rateLimiterTypeSerializer)],
+]) as unknown as Serializer<RateLimitParams>
+
+// the indexes of the PeerConfigParam variants
+const PEER_CONFIG_PARAM_OUTBOUND_RATE_LIMIT = 3
+const PEER_CONFIG_PARAM_INBOUND_RATE_LIMIT = 4
+
+/**
+ * Build set_peer_config to set the outbound or the inbound rate limiter of a peer, or to remove it
+ * with null. The SDK encodes the RateLimitParams of the baseline program, without the limiter type.
+ */
+export const setRateLimit = (
+    admin: PublicKey,
+    programId: PublicKey,
+    oftStore: PublicKey,
+    remoteEid: number,
+    direction: 'outbound' | 'inbound',
+    params: RateLimitParams | null
+): TransactionInstruction => {
+    const keys: AccountMeta[] = [
+        { pubkey: admin, isSigner: true, isWritable: true },
+        meta(derivePeer(programId, oftStore, remoteEid), true),
+        meta(oftStore),
+        meta(fromWeb3JsPublicKey(SystemProgram.programId)),
+    ]
+    const data = Buffer.concat([
+        instructionDiscriminator('set_peer_config'),
+        u32().serialize(remoteEid),
+        u8().serialize(
+            direction === 'outbound' ? PEER_CONFIG_PARAM_OUTBOUND_RATE_LIMIT : PEER_CONFIG_PARAM_INBOUND_RATE_LIMIT
+        ),
+        option(rateLimitParamsSerializer).serialize(params),
+    ])
+    return toWeb3JsInstruction(programId, keys, data)
+}
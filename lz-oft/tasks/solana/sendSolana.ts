@@ -1,7 +1,7 @@
 // tasks/solana/sendOFT.ts
 import { fetchMint, fetchToken, findAssociatedTokenPda } from '@metaplex-foundation/mpl-toolbox'
 import { publicKey, transactionBuilder } from '@metaplex-foundation/umi'
-import { fromWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters'
+import { fromWeb3JsInstruction, fromWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters'
 import { TOKEN_PROGRAM_ID } from '@solana/spl-token'
 import { PublicKey } from '@solana/web3.js'
 import bs58 from 'bs58'
@@ -9,18 +9,17 @@ import bs58 from 'bs58'
 import { createLogger } from '@layerzerolabs/io-devtools'
 import { EndpointId, endpointIdToNetwork } from '@layerzerolabs/lz-definitions'
 import { addressToBytes32 } from '@layerzerolabs/lz-v2-utilities'
-import { oft } from '@layerzerolabs/oft-v2-solana-sdk'
 
 import { SendResult } from '../common/types'
 import { DebugLogger, KnownErrors } from '../common/utils'
 
+import { derivePeer, fetchOftStore, fetchPeerConfig, quoteSend, send } from './oftProgram'
 import { parseDecimalToUnits, silenceSolana429 } from './utils'
 
 import {
     TransactionType,
     addComputeUnitInstructions,
     deriveConnection,
-    getAddressLookupTable,
     getLayerZeroScanLink,
     getSolanaDeployment,
 } from './index'
@@ -39,6 +38,9 @@ export interface SolanaArgs {
     oftProgramId?: string
     tokenProgram?: string
     computeUnitPriceScaleFactor?: number
+    referrer?: string // token account of the referrer
+    referralFeeBps?: number // share of the OFT fee paid to the referrer
+    toTokenAccount?: boolean // `to` is a token account, requires a peer with token_account_dest
 }
 
 export async function sendSolana({
@@ -53,6 +55,9 @@ export async function sendSolana({
     minAmount,
     extraOptions,
     composeMsg,
+    referrer,
+    referralFeeBps = 0,
+    toTokenAccount = false,
 }: SolanaArgs): Promise<SendResult> {
     // 1️⃣ RPC + UMI
     const { connection, umi, umiWalletSigner } = await deriveConnection(srcEid)
@@ -73,7 +78,7 @@ export async function sendSolana({
 
     // 3️⃣ Decide your store PDA (override or from your on‐disk deployment)
     const storePda = oftAddress ? publicKey(oftAddress) : publicKey(getSolanaDeployment(srcEid).oftStore)
-    const oftStoreInfo = await oft.accounts.fetchOFTStore(umi, storePda)
+    const oftStoreInfo = await fetchOftStore(connection, storePda)
     const mintPk = new PublicKey(oftStoreInfo.tokenMint)
     const escrowPk = new PublicKey(oftStoreInfo.tokenEscrow)
 
@@ -97,51 +102,54 @@ export async function sendSolana({
     // 7️⃣ Quote (use our overridden `programId`)
     logger.info('Quoting the native gas cost for the send transaction...')
     const recipient = addressToBytes32(to)
-    const { nativeFee } = await oft.quote(
-        umi.rpc,
-        {
-            payer: umiWalletSigner.publicKey,
-            tokenMint: fromWeb3JsPublicKey(mintPk),
-            tokenEscrow: fromWeb3JsPublicKey(escrowPk),
-        },
-        {
-            payInLzToken: false,
-            to: Buffer.from(recipient),
-            dstEid: dstEid,
-            amountLd: amountUnits,
-            minAmountLd: minAmount ? parseDecimalToUnits(minAmount, decimals) : amountUnits,
-            options: Buffer.from(extraOptions ? extraOptions.toString() : ''),
-            composeMsg: composeMsg ? Buffer.from(composeMsg.toString()) : undefined,
-        },
-        { oft: programId }, // ← use override
-        [],
-        (await getAddressLookupTable(connection, umi, srcEid)).lookupTableAddress
-    )
+    const peerInfo = await fetchPeerConfig(connection, derivePeer(programId, storePda, dstEid))
+    const oftAccounts = {
+        programId,
+        oftStore: storePda,
+        tokenMint: fromWeb3JsPublicKey(mintPk),
+        peerAddress: peerInfo.peerAddress,
+        endpointProgram: oftStoreInfo.endpointProgram,
+    }
+    const params = {
+        dstEid,
+        to: recipient,
+        amountLd: amountUnits,
+        minAmountLd: minAmount ? parseDecimalToUnits(minAmount, decimals) : amountUnits,
+        options: Buffer.from(extraOptions ? extraOptions.toString() : ''),
+        composeMsg: composeMsg ? Buffer.from(composeMsg.toString()) : null,
+        toTokenAccount,
+    }
+    const { nativeFee } = await quoteSend(connection, umiWalletSigner.publicKey, oftAccounts, {
+        ...params,
+        payInLzToken: false,
+        sender: umiWalletSigner.publicKey,
+    })
 
     // 8️⃣ Send (again passing `programId`)
     logger.info('Sending the transaction...')
-    const ix = await oft.send(
-        umi.rpc,
+    const ix = await send(
+        connection,
+        umiWalletSigner.publicKey,
         {
-            payer: umiWalletSigner,
-            tokenMint: fromWeb3JsPublicKey(mintPk),
+            ...oftAccounts,
             tokenEscrow: fromWeb3JsPublicKey(escrowPk),
             tokenSource: tokenAccount[0],
+            tokenProgram: tokenProgramId,
         },
         {
-            to: Buffer.from(recipient),
-            dstEid: dstEid,
-            amountLd: amountUnits,
-            minAmountLd: minAmount ? parseDecimalToUnits(minAmount, decimals) : amountUnits,
-            options: Buffer.from(extraOptions ? extraOptions.toString() : ''),
-            composeMsg: composeMsg ? Buffer.from(composeMsg.toString()) : undefined,
-            nativeFee: nativeFee,
-        },
-        { oft: programId, token: tokenProgramId } // ← use override
+            ...params,
+            nativeFee,
+            lzTokenFee: 0n,
+            referral: referrer ? { referrer: publicKey(referrer), feeBps: referralFeeBps } : null,
+        }
     )
 
     // 9️⃣ Compute units & submit
-    let txB = transactionBuilder().add([ix])
+    let txB = transactionBuilder().add({
+        instruction: fromWeb3JsInstruction(ix),
+        signers: [umiWalletSigner],
+        bytesCreatedOnChain: 0,
+    })
     txB = await addComputeUnitInstructions(
         connection,
         umi,